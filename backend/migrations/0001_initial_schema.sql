@@ -0,0 +1,81 @@
+-- Baseline schema for the CodeWithPapers database.
+--
+-- Mirrors the tables originally created by the Python loading scripts so a
+-- fresh database can be bootstrapped with `sqlx migrate run`. Every statement
+-- is idempotent, so applying it to the existing production database is a no-op.
+
+CREATE EXTENSION IF NOT EXISTS pgcrypto;
+
+CREATE TABLE IF NOT EXISTS papers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    title TEXT NOT NULL,
+    abstract TEXT,
+    arxiv_id VARCHAR(20) UNIQUE,
+    arxiv_url TEXT,
+    pdf_url TEXT,
+    published_date DATE,
+    authors JSONB,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS datasets (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    modalities TEXT[],
+    task_categories TEXT[],
+    languages TEXT[],
+    size TEXT,
+    homepage_url TEXT,
+    github_url TEXT,
+    paper_url TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS benchmarks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    dataset_id UUID REFERENCES datasets(id) ON DELETE SET NULL,
+    task TEXT NOT NULL,
+    description TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (name, dataset_id)
+);
+
+CREATE TABLE IF NOT EXISTS implementations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    paper_id UUID REFERENCES papers(id) ON DELETE CASCADE,
+    github_url TEXT NOT NULL,
+    framework VARCHAR(50),
+    stars INTEGER,
+    is_official BOOLEAN DEFAULT FALSE,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (paper_id, github_url)
+);
+
+CREATE TABLE IF NOT EXISTS benchmark_results (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    paper_id UUID REFERENCES papers(id) ON DELETE CASCADE,
+    benchmark_id UUID REFERENCES benchmarks(id) ON DELETE CASCADE,
+    implementation_id UUID REFERENCES implementations(id) ON DELETE SET NULL,
+    metric_name TEXT NOT NULL,
+    metric_value NUMERIC NOT NULL,
+    extra_data JSONB,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (paper_id, benchmark_id, metric_name)
+);
+
+CREATE TABLE IF NOT EXISTS paper_datasets (
+    paper_id UUID REFERENCES papers(id) ON DELETE CASCADE,
+    dataset_id UUID REFERENCES datasets(id) ON DELETE CASCADE,
+    PRIMARY KEY (paper_id, dataset_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_papers_published_date ON papers (published_date);
+CREATE INDEX IF NOT EXISTS idx_implementations_paper_id ON implementations (paper_id);
+CREATE INDEX IF NOT EXISTS idx_benchmark_results_paper_id ON benchmark_results (paper_id);
+CREATE INDEX IF NOT EXISTS idx_benchmark_results_benchmark_id ON benchmark_results (benchmark_id);
@@ -0,0 +1,12 @@
+-- Reproducibility signals detected by the GitHub scraper.
+--
+-- NULL means the repository has not been inspected yet; FALSE means it was
+-- inspected and the artifact is missing.
+
+ALTER TABLE implementations
+    ADD COLUMN IF NOT EXISTS has_dockerfile BOOLEAN,
+    ADD COLUMN IF NOT EXISTS has_conda_env BOOLEAN,
+    ADD COLUMN IF NOT EXISTS has_pinned_requirements BOOLEAN;
+
+CREATE INDEX IF NOT EXISTS idx_implementations_reproducible ON implementations (paper_id)
+    WHERE has_dockerfile OR has_conda_env OR has_pinned_requirements;
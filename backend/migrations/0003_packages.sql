@@ -0,0 +1,15 @@
+-- Package registry entries (PyPI, crates.io, npm) published from an
+-- implementation's repository, discovered by the GitHub scraper.
+
+CREATE TABLE IF NOT EXISTS packages (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    implementation_id UUID NOT NULL REFERENCES implementations(id) ON DELETE CASCADE,
+    registry TEXT NOT NULL CHECK (registry IN ('pypi', 'crates', 'npm')),
+    name TEXT NOT NULL,
+    url TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (implementation_id, registry, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_packages_registry_name ON packages (registry, name);
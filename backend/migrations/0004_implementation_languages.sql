@@ -0,0 +1,5 @@
+-- GitHub's language byte breakdown per repository, e.g. {"Python": 123456, "Cuda": 7890}.
+-- Kept separate from `framework`, which describes the ML framework rather than the language.
+
+ALTER TABLE implementations
+    ADD COLUMN IF NOT EXISTS languages JSONB;
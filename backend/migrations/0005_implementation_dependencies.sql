@@ -0,0 +1,14 @@
+-- Direct Python dependencies declared by an implementation's repository
+-- (requirements.txt / pyproject.toml), discovered by the GitHub scraper.
+-- Names are normalized per PEP 503 (lowercase, runs of -_. collapsed to -).
+
+CREATE TABLE IF NOT EXISTS implementation_dependencies (
+    implementation_id UUID NOT NULL REFERENCES implementations(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    version_spec TEXT,
+    source TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (implementation_id, name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_implementation_dependencies_name ON implementation_dependencies (name);
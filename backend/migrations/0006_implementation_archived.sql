@@ -0,0 +1,5 @@
+-- GitHub archived/disabled flags, so abandoned repositories can be demoted.
+
+ALTER TABLE implementations
+    ADD COLUMN IF NOT EXISTS archived BOOLEAN,
+    ADD COLUMN IF NOT EXISTS disabled BOOLEAN;
@@ -0,0 +1,5 @@
+-- Plain-text abstract (LaTeX and HTML stripped), kept alongside the original.
+-- Filled on ingestion and by the clean_abstracts backfill job.
+
+ALTER TABLE papers
+    ADD COLUMN IF NOT EXISTS abstract_plain TEXT;
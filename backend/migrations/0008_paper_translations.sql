@@ -0,0 +1,16 @@
+-- Machine translations of paper titles and abstracts, written by the
+-- translate_abstracts worker and served via `?lang=` on paper endpoints.
+
+CREATE TABLE IF NOT EXISTS paper_translations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    paper_id UUID NOT NULL REFERENCES papers(id) ON DELETE CASCADE,
+    -- Lowercase language code, e.g. 'de' or 'pt-br'
+    language TEXT NOT NULL CHECK (language = lower(language)),
+    title TEXT,
+    abstract TEXT,
+    -- Translation backend that produced the text, e.g. 'deepl'
+    provider TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (paper_id, language)
+);
@@ -0,0 +1,7 @@
+-- DOI for papers, so journal-published (non-arXiv) papers can be represented.
+-- Stored normalized (lowercase, no resolver prefix); DOIs are case-insensitive.
+
+ALTER TABLE papers
+    ADD COLUMN IF NOT EXISTS doi TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_papers_doi ON papers (doi);
@@ -0,0 +1,22 @@
+-- ORCID iDs for paper authors. Authors stay a JSONB array of names on
+-- papers; rows here attach an ORCID to the author at `position` in that
+-- array, found by the orcid_enricher job.
+
+CREATE TABLE IF NOT EXISTS paper_authors (
+    paper_id UUID NOT NULL REFERENCES papers(id) ON DELETE CASCADE,
+    -- Index into papers.authors
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    orcid TEXT NOT NULL CHECK (orcid ~ '^[0-9]{4}-[0-9]{4}-[0-9]{4}-[0-9]{3}[0-9X]$'),
+    -- Where the ORCID came from, e.g. 'orcid_api'
+    source TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (paper_id, position)
+);
+
+CREATE INDEX IF NOT EXISTS idx_paper_authors_orcid ON paper_authors (orcid);
+
+-- When the enricher last searched ORCID for this paper's authors
+ALTER TABLE papers
+    ADD COLUMN IF NOT EXISTS orcid_checked_at TIMESTAMPTZ;
@@ -0,0 +1,11 @@
+-- arXiv version awareness. The same paper can be loaded more than once as
+-- different versions (2301.00001v1, 2301.00001v2, or unversioned), so derive
+-- the base ID and version number to group the rows and pick the latest one.
+
+ALTER TABLE papers
+    ADD COLUMN IF NOT EXISTS arxiv_base_id TEXT
+        GENERATED ALWAYS AS (regexp_replace(arxiv_id, 'v[0-9]+$', '')) STORED,
+    ADD COLUMN IF NOT EXISTS arxiv_version INTEGER
+        GENERATED ALWAYS AS (substring(arxiv_id FROM 'v([0-9]+)$')::integer) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_papers_arxiv_base_id ON papers (arxiv_base_id);
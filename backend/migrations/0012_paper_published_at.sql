@@ -0,0 +1,11 @@
+-- Precise publication time (arXiv v1 submission time), since many papers share
+-- a published_date and ordering by date alone is unstable. Papers without it
+-- fall back to midnight UTC on published_date.
+
+ALTER TABLE papers
+    ADD COLUMN IF NOT EXISTS published_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_papers_publication_time ON papers (
+    (COALESCE(published_at, published_date::timestamp AT TIME ZONE 'UTC')),
+    id
+);
@@ -0,0 +1,23 @@
+-- Soft deletes: retracted papers, spam and bad scrapes are hidden by setting
+-- deleted_at instead of deleting rows, so removals can be audited and undone.
+-- Read endpoints exclude these rows unless an admin asks for include_deleted.
+
+ALTER TABLE papers
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS deletion_reason TEXT;
+
+ALTER TABLE datasets
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS deletion_reason TEXT;
+
+ALTER TABLE benchmarks
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS deletion_reason TEXT;
+
+ALTER TABLE implementations
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS deletion_reason TEXT;
+
+ALTER TABLE benchmark_results
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS deletion_reason TEXT;
@@ -0,0 +1,42 @@
+-- Maintain updated_at in the database rather than in each writer. Bulk loaders
+-- and upserts that forget to set it break stale-first scraping and delta
+-- indexing, so every INSERT fills it in and every UPDATE bumps it.
+
+ALTER TABLE benchmark_results
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ;
+
+UPDATE benchmark_results SET updated_at = COALESCE(created_at, NOW())
+WHERE updated_at IS NULL;
+
+ALTER TABLE benchmark_results
+    ALTER COLUMN updated_at SET DEFAULT NOW();
+
+CREATE OR REPLACE FUNCTION set_updated_at() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        NEW.updated_at := COALESCE(NEW.updated_at, NOW());
+    ELSE
+        NEW.updated_at := NOW();
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DO $$
+DECLARE
+    table_name TEXT;
+BEGIN
+    FOREACH table_name IN ARRAY ARRAY[
+        'papers', 'datasets', 'benchmarks', 'implementations', 'benchmark_results',
+        'packages', 'paper_translations', 'paper_authors'
+    ]
+    LOOP
+        EXECUTE format('DROP TRIGGER IF EXISTS set_updated_at ON %I', table_name);
+        EXECUTE format(
+            'CREATE TRIGGER set_updated_at BEFORE INSERT OR UPDATE ON %I '
+            'FOR EACH ROW EXECUTE FUNCTION set_updated_at()',
+            table_name
+        );
+    END LOOP;
+END;
+$$;
@@ -0,0 +1,14 @@
+-- Repository metadata pushed by GitHub webhooks. github_repo is the
+-- lowercase "owner/repo" parsed from github_url, so webhook payloads can be
+-- matched to implementations without caring how the URL was written.
+
+ALTER TABLE implementations
+    ADD COLUMN IF NOT EXISTS pushed_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS github_repo TEXT GENERATED ALWAYS AS (
+        lower(regexp_replace(
+            substring(github_url FROM 'github\.com/([^/?#]+/[^/?#]+)'),
+            '\.git$', ''
+        ))
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_implementations_github_repo ON implementations (github_repo);
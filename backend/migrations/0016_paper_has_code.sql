@@ -0,0 +1,50 @@
+-- Materialized "has code" flag: whether a paper has any live implementation.
+-- Kept current by a trigger on implementations, so filters, search indexing
+-- and stats can read it instead of running an EXISTS subquery per paper.
+-- Changing it bumps papers.updated_at, which lets delta indexing notice.
+
+ALTER TABLE papers
+    ADD COLUMN IF NOT EXISTS has_code BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE papers
+SET has_code = EXISTS (
+    SELECT 1 FROM implementations i
+    WHERE i.paper_id = papers.id AND i.deleted_at IS NULL
+)
+WHERE has_code IS DISTINCT FROM EXISTS (
+    SELECT 1 FROM implementations i
+    WHERE i.paper_id = papers.id AND i.deleted_at IS NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_papers_has_code ON papers (has_code) WHERE deleted_at IS NULL;
+
+CREATE OR REPLACE FUNCTION refresh_paper_has_code() RETURNS trigger AS $$
+DECLARE
+    paper_ids UUID[] := ARRAY[]::UUID[];
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        paper_ids := paper_ids || OLD.paper_id;
+    END IF;
+    IF TG_OP <> 'DELETE' THEN
+        paper_ids := paper_ids || NEW.paper_id;
+    END IF;
+
+    UPDATE papers
+    SET has_code = EXISTS (
+        SELECT 1 FROM implementations i
+        WHERE i.paper_id = papers.id AND i.deleted_at IS NULL
+    )
+    WHERE id = ANY(paper_ids)
+      AND has_code IS DISTINCT FROM EXISTS (
+        SELECT 1 FROM implementations i
+        WHERE i.paper_id = papers.id AND i.deleted_at IS NULL
+      );
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS refresh_paper_has_code ON implementations;
+CREATE TRIGGER refresh_paper_has_code
+    AFTER INSERT OR DELETE OR UPDATE OF paper_id, deleted_at ON implementations
+    FOR EACH ROW EXECUTE FUNCTION refresh_paper_has_code();
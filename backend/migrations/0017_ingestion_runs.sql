@@ -0,0 +1,13 @@
+-- One row per completed ingestion job (archive load, scrapers, submissions),
+-- so /api/stats can report when each source last brought in data.
+
+CREATE TABLE IF NOT EXISTS ingestion_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    source TEXT NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    items_processed BIGINT NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_ingestion_runs_source_finished
+    ON ingestion_runs (source, finished_at DESC);
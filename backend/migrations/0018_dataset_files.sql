@@ -0,0 +1,22 @@
+-- Downloadable files for a dataset, with the size and SHA-256 needed to
+-- verify a download. Mirrors of the same file are separate rows sharing a
+-- sha256. Filled in by submissions and the hf_dataset_files scraper.
+
+CREATE TABLE IF NOT EXISTS dataset_files (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    dataset_id UUID NOT NULL REFERENCES datasets(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    format TEXT,
+    size_bytes BIGINT CHECK (size_bytes >= 0),
+    sha256 TEXT CHECK (sha256 ~ '^[0-9a-f]{64}$'),
+    source TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (dataset_id, url)
+);
+
+CREATE INDEX IF NOT EXISTS idx_dataset_files_sha256 ON dataset_files (sha256);
+
+DROP TRIGGER IF EXISTS set_updated_at ON dataset_files;
+CREATE TRIGGER set_updated_at BEFORE INSERT OR UPDATE ON dataset_files
+    FOR EACH ROW EXECUTE FUNCTION set_updated_at();
@@ -0,0 +1,60 @@
+-- Task taxonomy: every benchmark task name becomes a node that can be placed
+-- under a parent, e.g. Computer Vision > Object Detection > 3D Object
+-- Detection. Benchmarks keep referring to their task by name; new names are
+-- added as root nodes automatically, so loaders and scrapers need no changes.
+
+CREATE TABLE IF NOT EXISTS tasks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL UNIQUE,
+    parent_id UUID REFERENCES tasks(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_tasks_parent_id ON tasks (parent_id);
+CREATE INDEX IF NOT EXISTS idx_benchmarks_task ON benchmarks (task);
+
+INSERT INTO tasks (name)
+SELECT DISTINCT task FROM benchmarks
+ON CONFLICT (name) DO NOTHING;
+
+CREATE OR REPLACE FUNCTION ensure_benchmark_task() RETURNS trigger AS $$
+BEGIN
+    INSERT INTO tasks (name) VALUES (NEW.task) ON CONFLICT (name) DO NOTHING;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS ensure_benchmark_task ON benchmarks;
+CREATE TRIGGER ensure_benchmark_task
+    AFTER INSERT OR UPDATE OF task ON benchmarks
+    FOR EACH ROW EXECUTE FUNCTION ensure_benchmark_task();
+
+-- Reject parents that would make a task its own ancestor
+CREATE OR REPLACE FUNCTION check_task_parent() RETURNS trigger AS $$
+BEGIN
+    IF NEW.parent_id IS NOT NULL AND EXISTS (
+        WITH RECURSIVE ancestors (id) AS (
+            SELECT NEW.parent_id
+            UNION
+            SELECT t.parent_id FROM tasks t
+            JOIN ancestors a ON t.id = a.id
+            WHERE t.parent_id IS NOT NULL
+        )
+        SELECT 1 FROM ancestors WHERE id = NEW.id
+    ) THEN
+        RAISE EXCEPTION 'Task "%" cannot be placed under its own subtask', NEW.name
+            USING ERRCODE = 'check_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS check_task_parent ON tasks;
+CREATE TRIGGER check_task_parent
+    BEFORE INSERT OR UPDATE OF parent_id ON tasks
+    FOR EACH ROW EXECUTE FUNCTION check_task_parent();
+
+DROP TRIGGER IF EXISTS set_updated_at ON tasks;
+CREATE TRIGGER set_updated_at BEFORE INSERT OR UPDATE ON tasks
+    FOR EACH ROW EXECUTE FUNCTION set_updated_at();
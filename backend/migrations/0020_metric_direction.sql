@@ -0,0 +1,40 @@
+-- Whether a larger value is better for a metric, so leaderboards can rank
+-- error rates, FID or perplexity correctly. Metrics are matched to
+-- benchmark_results.metric_name case-insensitively; unlisted metrics are
+-- treated as higher-is-better.
+
+CREATE TABLE IF NOT EXISTS metrics (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    higher_is_better BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_metrics_name ON metrics (lower(name));
+
+INSERT INTO metrics (name, higher_is_better) VALUES
+    ('Error', FALSE),
+    ('Error rate', FALSE),
+    ('Top-1 Error', FALSE),
+    ('Top-5 Error', FALSE),
+    ('Test Error', FALSE),
+    ('Percentage error', FALSE),
+    ('WER', FALSE),
+    ('CER', FALSE),
+    ('FID', FALSE),
+    ('KID', FALSE),
+    ('Perplexity', FALSE),
+    ('Bits per character', FALSE),
+    ('BPC', FALSE),
+    ('MAE', FALSE),
+    ('MSE', FALSE),
+    ('RMSE', FALSE),
+    ('EER', FALSE),
+    ('Params', FALSE),
+    ('FLOPs', FALSE)
+ON CONFLICT (lower(name)) DO NOTHING;
+
+DROP TRIGGER IF EXISTS set_updated_at ON metrics;
+CREATE TRIGGER set_updated_at BEFORE INSERT OR UPDATE ON metrics
+    FOR EACH ROW EXECUTE FUNCTION set_updated_at();
@@ -0,0 +1,100 @@
+-- Treat the arXiv versions of a paper as one paper.
+--
+-- arxiv_base_id now also strips an `arXiv:` prefix (and surrounding spaces),
+-- matching backend::arxiv_base_id, so `arXiv:2301.00001v2` and `2301.00001v1`
+-- group together. Generated expressions can't be altered before Postgres 17,
+-- so the column is re-added when it still has the 0011 definition.
+--
+-- has_code becomes a property of the whole version group: a paper has code
+-- when any of its versions has a live implementation, so the latest version
+-- reflects code linked to older version rows.
+
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1
+        FROM pg_attrdef ad
+        JOIN pg_attribute a ON a.attrelid = ad.adrelid AND a.attnum = ad.adnum
+        WHERE ad.adrelid = 'papers'::regclass
+          AND a.attname = 'arxiv_base_id'
+          AND pg_get_expr(ad.adbin, ad.adrelid) LIKE '%arxiv:%'
+    ) THEN
+        ALTER TABLE papers DROP COLUMN IF EXISTS arxiv_base_id;
+        ALTER TABLE papers
+            ADD COLUMN arxiv_base_id TEXT GENERATED ALWAYS AS (
+                regexp_replace(regexp_replace(btrim(arxiv_id), '^arxiv:', '', 'i'), 'v[0-9]+$', '')
+            ) STORED;
+    END IF;
+END $$;
+
+CREATE INDEX IF NOT EXISTS idx_papers_arxiv_base_id ON papers (arxiv_base_id);
+
+-- Recompute has_code for the given papers, every version sharing their base
+-- IDs, and every paper with one of `base_ids` (a version group a paper left).
+CREATE OR REPLACE FUNCTION refresh_paper_group_has_code(paper_ids UUID[], base_ids TEXT[])
+RETURNS void AS $$
+    WITH affected AS (
+        SELECT p.id, EXISTS (
+            SELECT 1 FROM implementations i
+            JOIN papers v ON v.id = i.paper_id
+            WHERE i.deleted_at IS NULL
+              AND (v.id = p.id OR v.arxiv_base_id = p.arxiv_base_id)
+        ) AS has_code
+        FROM papers p
+        WHERE p.id = ANY(paper_ids)
+           OR p.arxiv_base_id = ANY(base_ids)
+           OR p.arxiv_base_id IN (SELECT arxiv_base_id FROM papers WHERE id = ANY(paper_ids))
+    )
+    UPDATE papers
+    SET has_code = affected.has_code
+    FROM affected
+    WHERE papers.id = affected.id
+      AND papers.has_code IS DISTINCT FROM affected.has_code;
+$$ LANGUAGE sql;
+
+-- Backfill: rows were correct per version, so only whole-group code is new
+UPDATE papers
+SET has_code = TRUE
+WHERE NOT has_code
+  AND arxiv_base_id IS NOT NULL
+  AND EXISTS (
+    SELECT 1 FROM implementations i
+    JOIN papers v ON v.id = i.paper_id
+    WHERE i.deleted_at IS NULL AND v.arxiv_base_id = papers.arxiv_base_id
+  );
+
+CREATE OR REPLACE FUNCTION refresh_paper_has_code() RETURNS trigger AS $$
+DECLARE
+    paper_ids UUID[] := ARRAY[]::UUID[];
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        paper_ids := paper_ids || OLD.paper_id;
+    END IF;
+    IF TG_OP <> 'DELETE' THEN
+        paper_ids := paper_ids || NEW.paper_id;
+    END IF;
+
+    PERFORM refresh_paper_group_has_code(paper_ids, ARRAY[]::TEXT[]);
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- A new version row picks up its group's code, and a row whose arxiv_id
+-- changes updates both the group it joins and the one it leaves
+CREATE OR REPLACE FUNCTION refresh_arxiv_version_has_code() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        PERFORM refresh_paper_group_has_code(ARRAY[NEW.id], ARRAY[OLD.arxiv_base_id]);
+    ELSIF NEW.arxiv_base_id IS NOT NULL THEN
+        PERFORM refresh_paper_group_has_code(ARRAY[NEW.id], ARRAY[]::TEXT[]);
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS refresh_arxiv_version_has_code ON papers;
+CREATE TRIGGER refresh_arxiv_version_has_code
+    AFTER INSERT OR UPDATE OF arxiv_id ON papers
+    FOR EACH ROW EXECUTE FUNCTION refresh_arxiv_version_has_code();
@@ -0,0 +1,289 @@
+-- SQLite schema for local development (`DATABASE_URL=sqlite://...`).
+--
+-- A condensed copy of the Postgres schema in ../migrations, limited to the
+-- tables the API reads. Column names and meanings match Postgres so
+-- the API returns the same JSON; the differences are storage details:
+--
+-- * UUIDs are 16-byte BLOBs and timestamps RFC 3339 TEXT
+-- * JSONB and TEXT[] columns are JSON TEXT
+-- * NUMERIC metric values are TEXT, so no precision is lost
+-- * arxiv_base_id, arxiv_version and github_repo are plain columns filled by
+--   the writer (SQLite has no regexp_replace), see backend::arxiv_base_id and
+--   backend::github_repo
+-- * the task cycle check is done by SqliteRepository::set_task_parent
+--
+-- When a Postgres migration changes one of these tables, add a matching
+-- migration here.
+
+CREATE TABLE papers (
+    id BLOB PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    title TEXT NOT NULL,
+    abstract TEXT,
+    abstract_plain TEXT,
+    arxiv_id TEXT UNIQUE,
+    arxiv_base_id TEXT,
+    arxiv_version INTEGER,
+    arxiv_url TEXT,
+    pdf_url TEXT,
+    doi TEXT UNIQUE,
+    published_date DATE,
+    published_at DATETIME,
+    authors TEXT,
+    has_code BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    deleted_at DATETIME,
+    deletion_reason TEXT
+);
+
+CREATE INDEX idx_papers_arxiv_base_id ON papers (arxiv_base_id);
+CREATE INDEX idx_papers_published_date ON papers (published_date);
+
+CREATE TABLE paper_authors (
+    paper_id BLOB NOT NULL REFERENCES papers(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    orcid TEXT NOT NULL,
+    source TEXT NOT NULL,
+    created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    PRIMARY KEY (paper_id, position)
+);
+
+CREATE TABLE paper_translations (
+    id BLOB PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    paper_id BLOB NOT NULL REFERENCES papers(id) ON DELETE CASCADE,
+    language TEXT NOT NULL CHECK (language = lower(language)),
+    title TEXT,
+    abstract TEXT,
+    provider TEXT NOT NULL,
+    created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE (paper_id, language)
+);
+
+CREATE TABLE datasets (
+    id BLOB PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    modalities TEXT,
+    task_categories TEXT,
+    languages TEXT,
+    size TEXT,
+    homepage_url TEXT,
+    github_url TEXT,
+    paper_url TEXT,
+    created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    deleted_at DATETIME,
+    deletion_reason TEXT
+);
+
+CREATE TABLE dataset_files (
+    id BLOB PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    dataset_id BLOB NOT NULL REFERENCES datasets(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    format TEXT,
+    size_bytes INTEGER CHECK (size_bytes >= 0),
+    sha256 TEXT,
+    source TEXT NOT NULL,
+    created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE (dataset_id, url)
+);
+
+CREATE TABLE benchmarks (
+    id BLOB PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    name TEXT NOT NULL,
+    dataset_id BLOB REFERENCES datasets(id) ON DELETE SET NULL,
+    task TEXT NOT NULL,
+    description TEXT,
+    created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    deleted_at DATETIME,
+    deletion_reason TEXT,
+    UNIQUE (name, dataset_id)
+);
+
+CREATE INDEX idx_benchmarks_task ON benchmarks (task);
+
+CREATE TABLE tasks (
+    id BLOB PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    name TEXT NOT NULL UNIQUE,
+    parent_id BLOB REFERENCES tasks(id) ON DELETE SET NULL,
+    created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE INDEX idx_tasks_parent_id ON tasks (parent_id);
+
+CREATE TABLE implementations (
+    id BLOB PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    paper_id BLOB REFERENCES papers(id) ON DELETE CASCADE,
+    github_url TEXT NOT NULL,
+    github_repo TEXT,
+    framework TEXT,
+    stars INTEGER,
+    is_official BOOLEAN DEFAULT FALSE,
+    has_dockerfile BOOLEAN,
+    has_conda_env BOOLEAN,
+    has_pinned_requirements BOOLEAN,
+    languages TEXT,
+    archived BOOLEAN,
+    disabled BOOLEAN,
+    pushed_at DATETIME,
+    created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    deleted_at DATETIME,
+    deletion_reason TEXT,
+    UNIQUE (paper_id, github_url)
+);
+
+CREATE INDEX idx_implementations_paper_id ON implementations (paper_id);
+CREATE INDEX idx_implementations_github_repo ON implementations (github_repo);
+
+CREATE TABLE packages (
+    id BLOB PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    implementation_id BLOB NOT NULL REFERENCES implementations(id) ON DELETE CASCADE,
+    registry TEXT NOT NULL CHECK (registry IN ('pypi', 'crates', 'npm')),
+    name TEXT NOT NULL,
+    url TEXT NOT NULL,
+    created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    UNIQUE (implementation_id, registry, name)
+);
+
+CREATE TABLE implementation_dependencies (
+    implementation_id BLOB NOT NULL REFERENCES implementations(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    version_spec TEXT,
+    source TEXT NOT NULL,
+    created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    PRIMARY KEY (implementation_id, name)
+);
+
+CREATE TABLE benchmark_results (
+    id BLOB PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    paper_id BLOB REFERENCES papers(id) ON DELETE CASCADE,
+    benchmark_id BLOB REFERENCES benchmarks(id) ON DELETE CASCADE,
+    implementation_id BLOB REFERENCES implementations(id) ON DELETE SET NULL,
+    metric_name TEXT NOT NULL,
+    metric_value TEXT NOT NULL,
+    extra_data TEXT,
+    created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    deleted_at DATETIME,
+    deletion_reason TEXT,
+    UNIQUE (paper_id, benchmark_id, metric_name)
+);
+
+CREATE INDEX idx_benchmark_results_benchmark_id ON benchmark_results (benchmark_id);
+
+CREATE TABLE metrics (
+    id BLOB PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    name TEXT NOT NULL,
+    higher_is_better BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    updated_at DATETIME DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+);
+
+CREATE UNIQUE INDEX idx_metrics_name ON metrics (lower(name));
+
+INSERT INTO metrics (name, higher_is_better) VALUES
+    ('Error', FALSE),
+    ('Error rate', FALSE),
+    ('Top-1 Error', FALSE),
+    ('Top-5 Error', FALSE),
+    ('Test Error', FALSE),
+    ('Percentage error', FALSE),
+    ('WER', FALSE),
+    ('CER', FALSE),
+    ('FID', FALSE),
+    ('KID', FALSE),
+    ('Perplexity', FALSE),
+    ('Bits per character', FALSE),
+    ('BPC', FALSE),
+    ('MAE', FALSE),
+    ('MSE', FALSE),
+    ('RMSE', FALSE),
+    ('EER', FALSE),
+    ('Params', FALSE),
+    ('FLOPs', FALSE);
+
+CREATE TABLE ingestion_runs (
+    id BLOB PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    source TEXT NOT NULL,
+    started_at DATETIME NOT NULL,
+    finished_at DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now')),
+    items_processed INTEGER NOT NULL DEFAULT 0
+);
+
+-- has_code, as maintained by the refresh_paper_has_code trigger in Postgres:
+-- a paper has code when any arXiv version of it has a live implementation
+CREATE TRIGGER refresh_paper_has_code_insert AFTER INSERT ON implementations
+BEGIN
+    UPDATE papers
+    SET has_code = EXISTS (
+        SELECT 1 FROM implementations i
+        JOIN papers v ON v.id = i.paper_id
+        WHERE i.deleted_at IS NULL
+          AND (v.id = papers.id OR v.arxiv_base_id = papers.arxiv_base_id)
+    )
+    WHERE id = NEW.paper_id
+       OR arxiv_base_id = (SELECT arxiv_base_id FROM papers WHERE id = NEW.paper_id);
+END;
+
+CREATE TRIGGER refresh_paper_has_code_update AFTER UPDATE OF paper_id, deleted_at ON implementations
+BEGIN
+    UPDATE papers
+    SET has_code = EXISTS (
+        SELECT 1 FROM implementations i
+        JOIN papers v ON v.id = i.paper_id
+        WHERE i.deleted_at IS NULL
+          AND (v.id = papers.id OR v.arxiv_base_id = papers.arxiv_base_id)
+    )
+    WHERE id IN (OLD.paper_id, NEW.paper_id)
+       OR arxiv_base_id IN (
+           SELECT arxiv_base_id FROM papers WHERE id IN (OLD.paper_id, NEW.paper_id)
+       );
+END;
+
+CREATE TRIGGER refresh_paper_has_code_delete AFTER DELETE ON implementations
+BEGIN
+    UPDATE papers
+    SET has_code = EXISTS (
+        SELECT 1 FROM implementations i
+        JOIN papers v ON v.id = i.paper_id
+        WHERE i.deleted_at IS NULL
+          AND (v.id = papers.id OR v.arxiv_base_id = papers.arxiv_base_id)
+    )
+    WHERE id = OLD.paper_id
+       OR arxiv_base_id = (SELECT arxiv_base_id FROM papers WHERE id = OLD.paper_id);
+END;
+
+CREATE TRIGGER refresh_arxiv_version_has_code AFTER INSERT ON papers
+WHEN NEW.arxiv_base_id IS NOT NULL
+BEGIN
+    UPDATE papers
+    SET has_code = EXISTS (
+        SELECT 1 FROM implementations i
+        JOIN papers v ON v.id = i.paper_id
+        WHERE i.deleted_at IS NULL AND v.arxiv_base_id = NEW.arxiv_base_id
+    )
+    WHERE id = NEW.id;
+END;
+
+-- Every benchmark task is a node in the taxonomy, as ensure_benchmark_task
+-- does in Postgres. Not INSERT OR IGNORE: an upsert into benchmarks would
+-- override it with ABORT.
+CREATE TRIGGER ensure_benchmark_task_insert AFTER INSERT ON benchmarks
+BEGIN
+    INSERT INTO tasks (name)
+    SELECT NEW.task WHERE NOT EXISTS (SELECT 1 FROM tasks WHERE name = NEW.task);
+END;
+
+CREATE TRIGGER ensure_benchmark_task_update AFTER UPDATE OF task ON benchmarks
+BEGIN
+    INSERT INTO tasks (name)
+    SELECT NEW.task WHERE NOT EXISTS (SELECT 1 FROM tasks WHERE name = NEW.task);
+END;
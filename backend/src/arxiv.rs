@@ -0,0 +1,217 @@
+//! Client for the arXiv API (export.arxiv.org), which answers with Atom feeds.
+//!
+//! arXiv asks clients to wait 3 seconds between requests; callers are
+//! responsible for pacing.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::time::Duration;
+
+const ARXIV_API: &str = "http://export.arxiv.org/api/query";
+const ABS_PREFIXES: [&str; 2] = ["http://arxiv.org/abs/", "https://arxiv.org/abs/"];
+
+/// A paper as listed by the arXiv API.
+#[derive(Debug, Clone)]
+pub struct ArxivPaper {
+    /// Versioned arXiv ID, e.g. `2301.00001v2`
+    pub arxiv_id: String,
+    pub title: String,
+    pub summary: String,
+    pub authors: Vec<String>,
+    /// Submission time of the first version
+    pub published: DateTime<Utc>,
+    /// Submission time of this version
+    pub updated: DateTime<Utc>,
+    pub pdf_url: Option<String>,
+    /// Primary category first, e.g. `cs.CV`
+    pub categories: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Feed {
+    #[serde(default)]
+    entry: Vec<Entry>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    id: String,
+    title: String,
+    #[serde(default)]
+    summary: String,
+    published: DateTime<Utc>,
+    updated: DateTime<Utc>,
+    #[serde(default)]
+    author: Vec<Author>,
+    #[serde(default)]
+    link: Vec<Link>,
+    #[serde(default)]
+    category: Vec<Category>,
+}
+
+#[derive(Deserialize)]
+struct Author {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Link {
+    #[serde(rename = "@href")]
+    href: String,
+    #[serde(rename = "@title")]
+    title: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Category {
+    #[serde(rename = "@term")]
+    term: String,
+}
+
+/// Collapse the line breaks arXiv leaves in titles and abstracts.
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse an arXiv API Atom feed. Error entries (which arXiv returns in place
+/// of results for malformed queries) are skipped.
+pub fn parse_feed(xml: &str) -> Result<Vec<ArxivPaper>> {
+    let feed: Feed = quick_xml::de::from_str(xml).context("Invalid arXiv Atom feed")?;
+
+    Ok(feed
+        .entry
+        .into_iter()
+        .filter_map(|entry| {
+            let arxiv_id = ABS_PREFIXES
+                .iter()
+                .find_map(|prefix| entry.id.strip_prefix(prefix))?
+                .to_string();
+
+            Some(ArxivPaper {
+                arxiv_id,
+                title: collapse_whitespace(&entry.title),
+                summary: collapse_whitespace(&entry.summary),
+                authors: entry.author.into_iter().map(|a| a.name).collect(),
+                published: entry.published,
+                updated: entry.updated,
+                pdf_url: entry
+                    .link
+                    .into_iter()
+                    .find(|link| link.title.as_deref() == Some("pdf"))
+                    .map(|link| link.href),
+                categories: entry.category.into_iter().map(|c| c.term).collect(),
+            })
+        })
+        .collect())
+}
+
+/// Thin HTTP client for the arXiv query API.
+pub struct ArxivClient {
+    client: reqwest::Client,
+}
+
+impl ArxivClient {
+    pub fn new() -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent("CodeWithPapers/1.0 (https://github.com/GeorgePearse/codewithpapers)")
+            .timeout(Duration::from_secs(60))
+            .build()
+            .context("Failed to build HTTP client")?;
+
+        Ok(Self { client })
+    }
+
+    async fn query(&self, params: &[(&str, &str)]) -> Result<Vec<ArxivPaper>> {
+        let body = self
+            .client
+            .get(ARXIV_API)
+            .query(params)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        parse_feed(&body)
+    }
+
+    /// Look papers up by arXiv ID. Unversioned IDs return the latest version.
+    pub async fn fetch_by_ids(&self, arxiv_ids: &[String]) -> Result<Vec<ArxivPaper>> {
+        if arxiv_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let id_list = arxiv_ids.join(",");
+        let max_results = arxiv_ids.len().to_string();
+        self.query(&[("id_list", &id_list), ("max_results", &max_results)])
+            .await
+    }
+
+    /// The most recent submissions in any of the given categories (e.g.
+    /// `cs.LG`), newest first.
+    pub async fn fetch_recent(
+        &self,
+        categories: &[String],
+        max_results: usize,
+    ) -> Result<Vec<ArxivPaper>> {
+        if categories.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let search_query = categories
+            .iter()
+            .map(|category| format!("cat:{}", category))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        let max_results = max_results.to_string();
+        self.query(&[
+            ("search_query", search_query.as_str()),
+            ("sortBy", "submittedDate"),
+            ("sortOrder", "descending"),
+            ("max_results", max_results.as_str()),
+        ])
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arxiv_feed_is_parsed() {
+        let feed = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:arxiv="http://arxiv.org/schemas/atom">
+  <title type="html">ArXiv Query: id_list=1706.03762</title>
+  <id>http://arxiv.org/api/abc</id>
+  <updated>2024-01-01T00:00:00-05:00</updated>
+  <entry>
+    <id>http://arxiv.org/abs/1706.03762v7</id>
+    <updated>2023-08-02T00:41:18Z</updated>
+    <published>2017-06-12T17:57:34Z</published>
+    <title>Attention Is All You
+  Need</title>
+    <summary>  The dominant sequence transduction models are based on complex
+recurrent or convolutional neural networks.</summary>
+    <author><name>Ashish Vaswani</name></author>
+    <author><name>Noam Shazeer</name></author>
+    <arxiv:comment>15 pages, 5 figures</arxiv:comment>
+    <link href="http://arxiv.org/abs/1706.03762v7" rel="alternate" type="text/html"/>
+    <link title="pdf" href="http://arxiv.org/pdf/1706.03762v7" rel="related" type="application/pdf"/>
+    <arxiv:primary_category term="cs.CL" scheme="http://arxiv.org/schemas/atom"/>
+    <category term="cs.CL" scheme="http://arxiv.org/schemas/atom"/>
+    <category term="cs.LG" scheme="http://arxiv.org/schemas/atom"/>
+  </entry>
+</feed>"#;
+
+        let papers = parse_feed(feed).unwrap();
+        assert_eq!(papers.len(), 1);
+        assert_eq!(papers[0].arxiv_id, "1706.03762v7");
+        assert_eq!(papers[0].title, "Attention Is All You Need");
+        assert_eq!(papers[0].authors, ["Ashish Vaswani", "Noam Shazeer"]);
+        assert_eq!(papers[0].published.to_rfc3339(), "2017-06-12T17:57:34+00:00");
+        assert_eq!(papers[0].pdf_url.as_deref(), Some("http://arxiv.org/pdf/1706.03762v7"));
+        assert_eq!(papers[0].categories, ["cs.CL", "cs.LG"]);
+    }
+}
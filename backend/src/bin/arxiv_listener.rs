@@ -0,0 +1,360 @@
+//! arXiv Listener - Keeps the site current with new arXiv submissions
+//!
+//! Polls the arXiv API for the latest submissions in the configured
+//! categories and inserts papers that aren't in the database yet. New papers
+//! start without implementations; the code-link scrapers pick them up later.
+//! Each new paper is added to the Tantivy index (when one exists), announced
+//! on the `paper_events` Postgres channel, which `GET /api/papers/stream`
+//! relays as server-sent events, and optionally POSTed to a webhook.
+//!
+//! Usage:
+//!     arxiv_listener
+//!     arxiv_listener --categories cs.CV,cs.LG --interval-secs 900
+//!     arxiv_listener --once --dry-run
+
+use anyhow::{Context, Result};
+use backend::arxiv::{ArxivClient, ArxivPaper};
+use backend::search::{IndexConfig, SearchIndex};
+use backend::text::clean_abstract;
+use backend::{arxiv_base_id, record_ingestion_run, Paper, PaperEvent, PAPER_EVENTS_CHANNEL};
+use clap::Parser;
+use dotenvy::dotenv;
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::collections::HashSet;
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{debug, info, warn, Level};
+use tracing_subscriber::FmtSubscriber;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Poll arXiv for new papers and ingest them", long_about = None)]
+struct Args {
+    /// arXiv categories to follow
+    #[arg(short, long, value_delimiter = ',', default_value = "cs.CV,cs.LG,cs.CL,cs.AI")]
+    categories: Vec<String>,
+
+    /// Most recent submissions to fetch per poll
+    #[arg(short, long, default_value_t = 200)]
+    max_results: usize,
+
+    /// Seconds between polls (arXiv announces new papers once a day)
+    #[arg(short, long, default_value_t = 1800)]
+    interval_secs: u64,
+
+    /// Path of the Tantivy index to add new papers to
+    #[arg(long, default_value = "./data/tantivy_index")]
+    index_path: PathBuf,
+
+    /// URL to POST new papers to (can also use PAPER_WEBHOOK_URL env var)
+    #[arg(long)]
+    webhook_url: Option<String>,
+
+    /// Poll once and exit
+    #[arg(long, default_value_t = false)]
+    once: bool,
+
+    /// Dry run - don't write to database
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Verbose output
+    #[arg(short, long, default_value_t = false)]
+    verbose: bool,
+}
+
+#[derive(Debug, Default)]
+struct Stats {
+    polls: usize,
+    papers_seen: usize,
+    papers_inserted: usize,
+    papers_indexed: usize,
+    webhook_failures: usize,
+    errors: usize,
+}
+
+struct ArxivListener {
+    arxiv: ArxivClient,
+    client: reqwest::Client,
+    pool: PgPool,
+    search_index: Option<SearchIndex>,
+    webhook_url: Option<String>,
+    webhook_secret: Option<String>,
+    args: Args,
+    stats: Stats,
+}
+
+impl ArxivListener {
+    fn new(pool: PgPool, args: Args) -> Result<Self> {
+        // Only add to an existing index; build_search_index creates it
+        let search_index = if args.index_path.join("meta.json").exists() {
+            Some(SearchIndex::open(&args.index_path)?.with_config(IndexConfig::from_env()))
+        } else {
+            warn!(
+                "No search index at {:?}; new papers won't be indexed",
+                args.index_path
+            );
+            None
+        };
+
+        let webhook_url = args
+            .webhook_url
+            .clone()
+            .or_else(|| env::var("PAPER_WEBHOOK_URL").ok())
+            .filter(|url| !url.is_empty());
+        let webhook_secret = env::var("PAPER_WEBHOOK_SECRET")
+            .ok()
+            .filter(|secret| !secret.is_empty());
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()?;
+
+        Ok(Self {
+            arxiv: ArxivClient::new()?,
+            client,
+            pool,
+            search_index,
+            webhook_url,
+            webhook_secret,
+            args,
+            stats: Stats::default(),
+        })
+    }
+
+    /// Insert a paper unless any version of it is already stored, announcing
+    /// it on the events channel in the same transaction.
+    async fn insert_paper(&self, entry: &ArxivPaper) -> Result<Option<(Paper, PaperEvent)>> {
+        let base_id = arxiv_base_id(&entry.arxiv_id);
+        let mut tx = self.pool.begin().await?;
+
+        let paper: Option<Paper> = sqlx::query_as(
+            r#"
+            INSERT INTO papers (title, abstract, abstract_plain, arxiv_id, arxiv_url, pdf_url,
+                                published_date, published_at, authors)
+            SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9
+            WHERE NOT EXISTS (SELECT 1 FROM papers WHERE arxiv_base_id = $4)
+            ON CONFLICT (arxiv_id) DO NOTHING
+            RETURNING id, title, abstract, abstract_plain, arxiv_id, arxiv_url, pdf_url, doi,
+                      published_date, published_at, authors, has_code,
+                      created_at, updated_at, deleted_at
+            "#,
+        )
+        .bind(&entry.title)
+        .bind(&entry.summary)
+        .bind(clean_abstract(&entry.summary))
+        .bind(base_id)
+        .bind(format!("https://arxiv.org/abs/{}", base_id))
+        .bind(&entry.pdf_url)
+        .bind(entry.published.date_naive())
+        .bind(entry.published)
+        .bind(json!(entry.authors))
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(paper) = paper else {
+            return Ok(None);
+        };
+
+        let event = PaperEvent {
+            id: paper.id,
+            title: paper.title.clone(),
+            arxiv_id: paper.arxiv_id.clone(),
+            published_at: paper.published_at,
+            categories: entry.categories.clone(),
+        };
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(PAPER_EVENTS_CHANNEL)
+            .bind(serde_json::to_string(&event)?)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some((paper, event)))
+    }
+
+    fn index_papers(&mut self, papers: &[Paper]) -> Result<()> {
+        let Some(search_index) = &self.search_index else {
+            return Ok(());
+        };
+
+        // Take the writer lock only while indexing, so build_search_index
+        // can still rebuild the index between polls
+        let mut writer = search_index.writer()?;
+        for paper in papers {
+            writer.add_document(search_index.paper_to_document(paper))?;
+        }
+        writer.commit()?;
+        search_index.enforce_disk_budget(&writer)?;
+
+        self.stats.papers_indexed += papers.len();
+        Ok(())
+    }
+
+    /// POST new papers to the webhook, signed like GitHub's deliveries
+    /// (`X-Signature-256: sha256=<hex HMAC of body>`) when a secret is set.
+    async fn send_webhook(&self, events: &[PaperEvent]) -> Result<()> {
+        let Some(url) = &self.webhook_url else {
+            return Ok(());
+        };
+
+        let body = serde_json::to_vec(&json!({ "event": "papers.created", "papers": events }))?;
+        let mut request = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.webhook_secret {
+            let mut mac = Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes())?;
+            mac.update(&body);
+            let signature = hex::encode(mac.finalize().into_bytes());
+            request = request.header("X-Signature-256", format!("sha256={}", signature));
+        }
+
+        request.body(body).send().await?.error_for_status()?;
+        Ok(())
+    }
+
+    async fn poll(&mut self) -> Result<()> {
+        let started_at = chrono::Utc::now();
+        let entries = self
+            .arxiv
+            .fetch_recent(&self.args.categories, self.args.max_results)
+            .await
+            .context("arXiv request failed")?;
+        self.stats.polls += 1;
+        self.stats.papers_seen += entries.len();
+
+        let base_ids: Vec<String> = entries
+            .iter()
+            .map(|entry| arxiv_base_id(&entry.arxiv_id).to_string())
+            .collect();
+        let known: HashSet<String> = sqlx::query_scalar(
+            "SELECT arxiv_base_id FROM papers WHERE arxiv_base_id = ANY($1)",
+        )
+        .bind(&base_ids)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to look up known papers")?
+        .into_iter()
+        .collect();
+
+        let new_entries: Vec<&ArxivPaper> = entries
+            .iter()
+            .filter(|entry| !known.contains(arxiv_base_id(&entry.arxiv_id)))
+            .collect();
+        debug!("{} of {} submissions are new", new_entries.len(), entries.len());
+
+        if self.args.dry_run {
+            for entry in &new_entries {
+                info!("[DRY RUN] Would insert {}: {}", entry.arxiv_id, entry.title);
+            }
+            return Ok(());
+        }
+
+        let mut papers = Vec::new();
+        let mut events = Vec::new();
+        for entry in new_entries {
+            match self.insert_paper(entry).await {
+                Ok(Some((paper, event))) => {
+                    debug!("Inserted {}: {}", entry.arxiv_id, entry.title);
+                    papers.push(paper);
+                    events.push(event);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    warn!("Failed to insert {}: {:#}", entry.arxiv_id, e);
+                    self.stats.errors += 1;
+                }
+            }
+        }
+        self.stats.papers_inserted += papers.len();
+        let inserted = papers.len() as i64;
+        if let Err(e) = record_ingestion_run(&self.pool, "arxiv", started_at, inserted).await {
+            warn!("Failed to record ingestion run: {}", e);
+        }
+
+        if papers.is_empty() {
+            return Ok(());
+        }
+        if let Err(e) = self.index_papers(&papers) {
+            warn!("Failed to index new papers: {:#}", e);
+            self.stats.errors += 1;
+        }
+        if let Err(e) = self.send_webhook(&events).await {
+            warn!("Webhook delivery failed: {:#}", e);
+            self.stats.webhook_failures += 1;
+        }
+
+        info!("Inserted {} new papers", papers.len());
+        Ok(())
+    }
+
+    async fn run(&mut self) {
+        loop {
+            if let Err(e) = self.poll().await {
+                warn!("Poll failed: {:#}", e);
+                self.stats.errors += 1;
+            }
+            if self.args.once {
+                break;
+            }
+
+            tokio::select! {
+                _ = sleep(Duration::from_secs(self.args.interval_secs)) => {}
+                _ = tokio::signal::ctrl_c() => {
+                    info!("Shutting down...");
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    let args = Args::parse();
+
+    // Setup logging
+    let log_level = if args.verbose {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(log_level)
+        .with_target(false)
+        .compact()
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    info!("Starting arXiv listener for {}...", args.categories.join(", "));
+    if args.dry_run {
+        warn!("DRY RUN MODE - No database writes will occur");
+    }
+
+    let database_url = env::var("POSTGRES_URI").context("POSTGRES_URI must be set")?;
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+    info!("Connected to database");
+
+    let mut listener = ArxivListener::new(pool, args)?;
+    listener.run().await;
+
+    info!("=== arXiv Listener Statistics ===");
+    info!("Polls:            {}", listener.stats.polls);
+    info!("Papers seen:      {}", listener.stats.papers_seen);
+    info!("Papers inserted:  {}", listener.stats.papers_inserted);
+    info!("Papers indexed:   {}", listener.stats.papers_indexed);
+    info!("Webhook failures: {}", listener.stats.webhook_failures);
+    info!("Errors:           {}", listener.stats.errors);
+
+    Ok(())
+}
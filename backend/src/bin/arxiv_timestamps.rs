@@ -0,0 +1,190 @@
+//! arXiv Timestamps - Backfill exact publication times from the arXiv API
+//!
+//! Sets `published_at` to each arXiv paper's v1 submission time (and fills
+//! `published_date` where it is missing), so "latest papers" ordering is
+//! stable between papers published on the same day.
+//!
+//! Usage:
+//!     arxiv_timestamps
+//!     arxiv_timestamps --max-papers 5000 --dry-run
+
+use anyhow::{Context, Result};
+use backend::arxiv::ArxivClient;
+use backend::arxiv_base_id;
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::Parser;
+use dotenvy::dotenv;
+use sqlx::postgres::PgPoolOptions;
+use std::collections::HashMap;
+use std::env;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{debug, info, warn, Level};
+use tracing_subscriber::FmtSubscriber;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Backfill paper publication times from arXiv", long_about = None)]
+struct Args {
+    /// Papers per arXiv API request
+    #[arg(short, long, default_value_t = 100)]
+    batch_size: i64,
+
+    /// Maximum number of papers to process (0 = all)
+    #[arg(short, long, default_value_t = 0)]
+    max_papers: usize,
+
+    /// Delay between arXiv API requests in milliseconds (arXiv asks for 3s)
+    #[arg(short, long, default_value_t = 3000)]
+    delay_ms: u64,
+
+    /// Dry run - don't write to database
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Verbose output
+    #[arg(short, long, default_value_t = false)]
+    verbose: bool,
+}
+
+#[derive(Debug, Default)]
+struct Stats {
+    papers_processed: usize,
+    papers_updated: usize,
+    not_found: usize,
+    errors: usize,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    let args = Args::parse();
+
+    // Setup logging
+    let log_level = if args.verbose {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(log_level)
+        .with_target(false)
+        .compact()
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    info!("Starting arXiv timestamp backfill...");
+    if args.dry_run {
+        warn!("DRY RUN MODE - No database writes will occur");
+    }
+
+    let database_url = env::var("POSTGRES_URI").context("POSTGRES_URI must be set")?;
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+    info!("Connected to database");
+
+    let arxiv = ArxivClient::new()?;
+    let mut stats = Stats::default();
+    let mut last_id = uuid::Uuid::nil();
+
+    loop {
+        // Keyset pagination, since papers arXiv doesn't know keep a NULL published_at
+        let papers: Vec<(uuid::Uuid, String)> = sqlx::query_as(
+            r#"
+            SELECT id, arxiv_id
+            FROM papers
+            WHERE id > $1
+              AND arxiv_id IS NOT NULL
+              AND published_at IS NULL
+            ORDER BY id
+            LIMIT $2
+            "#,
+        )
+        .bind(last_id)
+        .bind(args.batch_size)
+        .fetch_all(&pool)
+        .await
+        .context("Failed to fetch papers")?;
+
+        let Some((batch_last_id, _)) = papers.last() else {
+            break;
+        };
+        last_id = *batch_last_id;
+
+        // Every version of a paper shares its v1 submission time
+        let arxiv_ids: Vec<String> = papers
+            .iter()
+            .map(|(_, arxiv_id)| arxiv_base_id(arxiv_id).to_string())
+            .collect();
+        let published: HashMap<String, DateTime<Utc>> = match arxiv.fetch_by_ids(&arxiv_ids).await
+        {
+            Ok(entries) => entries
+                .into_iter()
+                .map(|entry| (arxiv_base_id(&entry.arxiv_id).to_string(), entry.published))
+                .collect(),
+            Err(e) => {
+                warn!("arXiv request failed: {:#}", e);
+                stats.errors += 1;
+                sleep(Duration::from_millis(args.delay_ms)).await;
+                continue;
+            }
+        };
+
+        let mut ids = Vec::with_capacity(papers.len());
+        let mut published_ats = Vec::with_capacity(papers.len());
+        for (id, arxiv_id) in &papers {
+            match published.get(arxiv_base_id(arxiv_id)) {
+                Some(published_at) => {
+                    debug!("{} published at {}", arxiv_id, published_at);
+                    ids.push(*id);
+                    published_ats.push(*published_at);
+                }
+                None => stats.not_found += 1,
+            }
+        }
+        stats.papers_processed += papers.len();
+
+        if !args.dry_run && !ids.is_empty() {
+            let published_dates: Vec<NaiveDate> =
+                published_ats.iter().map(|at| at.date_naive()).collect();
+            sqlx::query(
+                r#"
+                UPDATE papers
+                SET published_at = batch.published_at,
+                    published_date = COALESCE(papers.published_date, batch.published_date)
+                FROM UNNEST($1::uuid[], $2::timestamptz[], $3::date[])
+                    AS batch(id, published_at, published_date)
+                WHERE papers.id = batch.id
+                "#,
+            )
+            .bind(&ids)
+            .bind(&published_ats)
+            .bind(&published_dates)
+            .execute(&pool)
+            .await
+            .context("Failed to update publication times")?;
+        }
+        stats.papers_updated += ids.len();
+
+        info!(
+            "Processed {} papers ({} updated)",
+            stats.papers_processed, stats.papers_updated
+        );
+
+        if args.max_papers > 0 && stats.papers_processed >= args.max_papers {
+            break;
+        }
+        sleep(Duration::from_millis(args.delay_ms)).await;
+    }
+
+    info!("=== arXiv Timestamp Statistics ===");
+    info!("Papers processed: {}", stats.papers_processed);
+    info!("Papers updated:   {}", stats.papers_updated);
+    info!("Not on arXiv:     {}", stats.not_found);
+    info!("Errors:           {}", stats.errors);
+
+    Ok(())
+}
@@ -15,8 +15,8 @@ use std::path::PathBuf;
 use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
-use backend::search::SearchIndex;
-use backend::Paper;
+use backend::search::{IndexConfig, SearchIndex};
+use backend::{latest_arxiv_version_filter, Paper};
 
 /// CLI arguments
 #[derive(Parser, Debug)]
@@ -98,27 +98,34 @@ async fn main() -> Result<()> {
 
     // Create or open index
     let search_index = SearchIndex::open_or_create(&args.index_path)
-        .context("Failed to create/open search index")?;
+        .context("Failed to create/open search index")?
+        .with_config(IndexConfig::from_env());
 
     info!("Index ready at {:?}", args.index_path);
 
-    // Create writer with 50MB heap
-    let mut writer = search_index.writer(50_000_000)?;
+    // Writer heap, merge policy and disk budget come from TANTIVY_* env vars
+    let mut writer = search_index.writer()?;
 
     let mut indexed_count = 0usize;
     let mut offset = 0i64;
 
     loop {
         // Fetch batch of papers
-        let papers: Vec<Paper> = sqlx::query_as(
+        let papers: Vec<Paper> = sqlx::query_as(&format!(
             r#"
-            SELECT id, title, abstract, arxiv_id, arxiv_url, pdf_url,
-                   published_date, authors, created_at, updated_at
+            SELECT id, title, abstract, abstract_plain, arxiv_id, arxiv_url, pdf_url, doi,
+                   published_date, published_at, authors, has_code,
+                   created_at, updated_at, deleted_at
             FROM papers
+            -- Only live papers, and only the latest arXiv version of each;
+            -- search maps older versions to it anyway
+            WHERE deleted_at IS NULL
+              AND {}
             ORDER BY id
             LIMIT $1 OFFSET $2
             "#,
-        )
+            latest_arxiv_version_filter()
+        ))
         .bind(args.batch_size)
         .bind(offset)
         .fetch_all(&pool)
@@ -145,6 +152,7 @@ async fn main() -> Result<()> {
                     (indexed_count as f64 / total_count as f64) * 100.0
                 );
                 writer.commit()?;
+                search_index.enforce_disk_budget(&writer)?;
             }
         }
 
@@ -162,6 +170,8 @@ async fn main() -> Result<()> {
     // Final commit
     info!("Final commit...");
     writer.commit()?;
+    writer.wait_merging_threads()?;
+    info!("Index size: {} MB", search_index.disk_usage()? / 1_000_000);
 
     info!(
         "Indexing complete! {} papers indexed to {:?}",
@@ -0,0 +1,140 @@
+//! Clean Abstracts - Backfill `abstract_plain` for existing papers
+//!
+//! Strips LaTeX markup and decodes HTML entities in each paper's abstract,
+//! storing the result in `abstract_plain` and leaving `abstract` untouched.
+//! New papers get `abstract_plain` on ingestion; this job covers papers
+//! loaded before that, or re-cleans everything after the rules change.
+//!
+//! Usage:
+//!     clean_abstracts
+//!     clean_abstracts --all --batch-size 5000
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use dotenvy::dotenv;
+use sqlx::postgres::PgPoolOptions;
+use std::env;
+use tracing::{info, Level};
+use tracing_subscriber::FmtSubscriber;
+
+use backend::text::clean_abstract;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Backfill cleaned plain-text paper abstracts", long_about = None)]
+struct Args {
+    /// Batch size for fetching and updating papers
+    #[arg(long, default_value_t = 1000)]
+    batch_size: i64,
+
+    /// Re-clean papers that already have a plain abstract
+    #[arg(long, default_value_t = false)]
+    all: bool,
+
+    /// Dry run - report changes without writing to the database
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Verbose output
+    #[arg(short, long, default_value_t = false)]
+    verbose: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    let args = Args::parse();
+
+    // Setup logging
+    let log_level = if args.verbose {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(log_level)
+        .with_target(false)
+        .compact()
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    let database_url = env::var("POSTGRES_URI").context("POSTGRES_URI must be set")?;
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+    info!("Connected to database");
+
+    let mut last_id = uuid::Uuid::nil();
+    let mut cleaned_count = 0usize;
+    let mut changed_count = 0usize;
+
+    loop {
+        // Keyset pagination, since updated rows drop out of an OFFSET window
+        let papers: Vec<(uuid::Uuid, String)> = sqlx::query_as(
+            r#"
+            SELECT id, abstract
+            FROM papers
+            WHERE id > $1
+              AND abstract IS NOT NULL
+              AND ($2 OR abstract_plain IS NULL)
+            ORDER BY id
+            LIMIT $3
+            "#,
+        )
+        .bind(last_id)
+        .bind(args.all)
+        .bind(args.batch_size)
+        .fetch_all(&pool)
+        .await
+        .context("Failed to fetch papers")?;
+
+        let Some((batch_last_id, _)) = papers.last() else {
+            break;
+        };
+        last_id = *batch_last_id;
+
+        let mut ids = Vec::with_capacity(papers.len());
+        let mut plain_abstracts = Vec::with_capacity(papers.len());
+        for (id, raw) in &papers {
+            let plain = clean_abstract(raw);
+            if plain != *raw {
+                changed_count += 1;
+            }
+            ids.push(*id);
+            plain_abstracts.push(plain);
+        }
+        cleaned_count += papers.len();
+
+        if !args.dry_run {
+            sqlx::query(
+                r#"
+                UPDATE papers
+                SET abstract_plain = batch.abstract_plain
+                FROM UNNEST($1::uuid[], $2::text[]) AS batch(id, abstract_plain)
+                WHERE papers.id = batch.id
+                "#,
+            )
+            .bind(&ids)
+            .bind(&plain_abstracts)
+            .execute(&pool)
+            .await
+            .context("Failed to update abstracts")?;
+        }
+
+        info!(
+            "Cleaned {} abstracts ({} had markup)",
+            cleaned_count, changed_count
+        );
+    }
+
+    info!(
+        "Done: {} abstracts cleaned, {} contained LaTeX or HTML{}",
+        cleaned_count,
+        changed_count,
+        if args.dry_run { " (dry run)" } else { "" }
+    );
+
+    Ok(())
+}
@@ -5,6 +5,8 @@
 use anyhow::{Context, Result};
 use arrow::array::{Array, StringArray};
 use arrow::record_batch::RecordBatch;
+use backend::record_ingestion_run;
+use backend::text::clean_abstract;
 use clap::Parser;
 use dotenvy::dotenv;
 use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
@@ -12,7 +14,7 @@ use sqlx::postgres::PgPoolOptions;
 use sqlx::PgPool;
 use std::env;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
@@ -56,10 +58,15 @@ async fn insert_paper_batch(
         return Ok(0);
     }
 
+    let abstracts_plain: Vec<Option<String>> = abstracts
+        .iter()
+        .map(|a| a.as_deref().map(clean_abstract))
+        .collect();
+
     let result = sqlx::query(
         r#"
-        INSERT INTO papers (title, abstract, arxiv_id, arxiv_url, pdf_url)
-        SELECT * FROM UNNEST($1::text[], $2::text[], $3::text[], $4::text[], $5::text[])
+        INSERT INTO papers (title, abstract, arxiv_id, arxiv_url, pdf_url, abstract_plain)
+        SELECT * FROM UNNEST($1::text[], $2::text[], $3::text[], $4::text[], $5::text[], $6::text[])
         ON CONFLICT (arxiv_id) DO NOTHING
         "#,
     )
@@ -68,6 +75,7 @@ async fn insert_paper_batch(
     .bind(arxiv_ids)
     .bind(arxiv_urls)
     .bind(pdf_urls)
+    .bind(&abstracts_plain)
     .execute(pool)
     .await?;
 
@@ -137,7 +145,7 @@ fn get_string_column(batch: &RecordBatch, col_idx: usize) -> Option<&StringArray
 
 async fn load_papers(
     pool: &PgPool,
-    data_dir: &PathBuf,
+    data_dir: &Path,
     batch_size: usize,
     stats: &mut LoaderStats,
 ) -> Result<()> {
@@ -233,11 +241,11 @@ async fn load_papers(
                         let chunk_end = (chunk_start + chunk_size).min(arxiv_ids.len());
                         match insert_paper_batch(
                             pool,
-                            &titles[chunk_start..chunk_end].to_vec(),
-                            &abstracts[chunk_start..chunk_end].to_vec(),
-                            &arxiv_ids[chunk_start..chunk_end].to_vec(),
-                            &arxiv_urls[chunk_start..chunk_end].to_vec(),
-                            &pdf_urls[chunk_start..chunk_end].to_vec(),
+                            &titles[chunk_start..chunk_end],
+                            &abstracts[chunk_start..chunk_end],
+                            &arxiv_ids[chunk_start..chunk_end],
+                            &arxiv_urls[chunk_start..chunk_end],
+                            &pdf_urls[chunk_start..chunk_end],
                         ).await {
                             Ok(inserted) => {
                                 stats.papers_inserted += inserted;
@@ -271,7 +279,7 @@ async fn load_papers(
 
 async fn load_datasets(
     pool: &PgPool,
-    data_dir: &PathBuf,
+    data_dir: &Path,
     batch_size: usize,
     stats: &mut LoaderStats,
 ) -> Result<()> {
@@ -346,7 +354,7 @@ async fn load_datasets(
 
 async fn load_links(
     pool: &PgPool,
-    data_dir: &PathBuf,
+    data_dir: &Path,
     batch_size: usize,
     stats: &mut LoaderStats,
 ) -> Result<()> {
@@ -450,6 +458,7 @@ async fn main() -> Result<()> {
         .finish();
     tracing::subscriber::set_global_default(subscriber)?;
 
+    let started_at = chrono::Utc::now();
     info!("Starting Optimized Data Loader (Arrow columnar + smaller batches)...");
     info!("Data directory: {:?}", args.data_dir);
     info!("Batch size: {}", args.batch_size);
@@ -489,6 +498,11 @@ async fn main() -> Result<()> {
     }
 
     print_stats(&stats);
+
+    let items = stats.papers_inserted + stats.datasets_inserted + stats.links_inserted;
+    if let Err(e) = record_ingestion_run(&pool, "archive", started_at, items as i64).await {
+        warn!("Failed to record ingestion run: {}", e);
+    }
     info!("Loading complete.");
 
     Ok(())
@@ -0,0 +1,611 @@
+//! GitHub App Sync - Bulk-refreshes repository metadata through the GraphQL API
+//!
+//! Authenticates as a GitHub App installation and fetches stars, last push
+//! time, archived/disabled status and the language breakdown for every
+//! tracked repository, 100 repositories per GraphQL query. This keeps
+//! `implementations` current far faster than the REST-per-repo
+//! `github_scraper`, which is still needed for reproducibility artifacts,
+//! packages and dependencies.
+//!
+//! Requires GITHUB_APP_ID, GITHUB_APP_INSTALLATION_ID and
+//! GITHUB_APP_PRIVATE_KEY_PATH (the app's PEM private key).
+//!
+//! Usage:
+//!     github_app_sync
+//!     github_app_sync --max-repos 1000 --dry-run
+
+use anyhow::{Context, Result};
+use backend::record_ingestion_run;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use clap::Parser;
+use dotenvy::dotenv;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::collections::HashMap;
+use std::env;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{debug, info, warn, Level};
+use tracing_subscriber::FmtSubscriber;
+
+const GITHUB_API: &str = "https://api.github.com";
+const USER_AGENT: &str = "CodeWithPapers/1.0 (https://github.com/GeorgePearse/codewithpapers)";
+
+/// GitHub caps a single GraphQL connection or query at 100 repositories.
+const MAX_REPOS_PER_QUERY: usize = 100;
+
+const REPOSITORY_FIELDS: &str = r#"
+fragment RepositoryFields on Repository {
+  stargazerCount
+  pushedAt
+  isArchived
+  isDisabled
+  languages(first: 100) {
+    edges {
+      size
+      node { name }
+    }
+  }
+}"#;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Bulk-refresh GitHub metadata as a GitHub App", long_about = None)]
+struct Args {
+    /// Repositories per GraphQL query (at most 100)
+    #[arg(short, long, default_value_t = 100)]
+    batch_size: usize,
+
+    /// Maximum number of repos to process (0 = all)
+    #[arg(short, long, default_value_t = 0)]
+    max_repos: usize,
+
+    /// Delay between GraphQL queries in milliseconds
+    #[arg(short, long, default_value_t = 500)]
+    delay_ms: u64,
+
+    /// Dry run - don't write to database
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Verbose output
+    #[arg(short, long, default_value_t = false)]
+    verbose: bool,
+}
+
+#[derive(Serialize)]
+struct AppClaims {
+    iat: i64,
+    exp: i64,
+    iss: String,
+}
+
+#[derive(Deserialize)]
+struct InstallationToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlResponse {
+    data: Option<HashMap<String, Option<serde_json::Value>>>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Repository {
+    stargazer_count: i32,
+    pushed_at: Option<DateTime<Utc>>,
+    is_archived: bool,
+    is_disabled: bool,
+    languages: Option<LanguageConnection>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageConnection {
+    edges: Vec<LanguageEdge>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageEdge {
+    size: i64,
+    node: LanguageNode,
+}
+
+#[derive(Debug, Deserialize)]
+struct LanguageNode {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RateLimit {
+    cost: i32,
+    remaining: i32,
+    reset_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+struct Stats {
+    repos_processed: usize,
+    repos_updated: usize,
+    implementations_updated: u64,
+    repos_not_found: usize,
+    queries: usize,
+    errors: usize,
+}
+
+/// Build one aliased query fetching every repository in the batch. Owners
+/// and names are passed as variables, so they never need escaping.
+fn build_query(repos: &[(String, String)]) -> (String, serde_json::Value) {
+    let mut params = Vec::with_capacity(repos.len() * 2);
+    let mut fields = String::new();
+    let mut variables = serde_json::Map::new();
+
+    for (i, (owner, name)) in repos.iter().enumerate() {
+        params.push(format!("$o{i}: String!, $n{i}: String!"));
+        fields.push_str(&format!(
+            "  r{i}: repository(owner: $o{i}, name: $n{i}) {{ ...RepositoryFields }}\n"
+        ));
+        variables.insert(format!("o{i}"), json!(owner));
+        variables.insert(format!("n{i}"), json!(name));
+    }
+
+    let query = format!(
+        "query({}) {{\n{}  rateLimit {{ cost remaining resetAt }}\n}}\n{}",
+        params.join(", "),
+        fields,
+        REPOSITORY_FIELDS
+    );
+    (query, serde_json::Value::Object(variables))
+}
+
+/// Repositories per query for a requested `--batch-size`.
+fn batch_size(requested: usize) -> usize {
+    requested.clamp(1, MAX_REPOS_PER_QUERY)
+}
+
+/// Match the `r{i}` aliases of a query's data back to the `count`
+/// repositories it asked for. Repositories returned as null (missing or
+/// inaccessible) or left out of the response map to `None`.
+fn parse_repositories(
+    mut data: HashMap<String, Option<serde_json::Value>>,
+    count: usize,
+) -> Result<Vec<Option<Repository>>> {
+    (0..count)
+        .map(|i| match data.remove(&format!("r{i}")).flatten() {
+            Some(value) => Ok(Some(serde_json::from_value(value)?)),
+            None => Ok(None),
+        })
+        .collect()
+}
+
+struct GitHubAppSync {
+    client: reqwest::Client,
+    pool: PgPool,
+    args: Args,
+    app_id: String,
+    installation_id: String,
+    key: EncodingKey,
+    token: Option<InstallationToken>,
+    stats: Stats,
+}
+
+impl GitHubAppSync {
+    fn new(pool: PgPool, args: Args) -> Result<Self> {
+        let app_id = env::var("GITHUB_APP_ID").context("GITHUB_APP_ID must be set")?;
+        let installation_id = env::var("GITHUB_APP_INSTALLATION_ID")
+            .context("GITHUB_APP_INSTALLATION_ID must be set")?;
+        let key_path = env::var("GITHUB_APP_PRIVATE_KEY_PATH")
+            .context("GITHUB_APP_PRIVATE_KEY_PATH must be set")?;
+        let pem = std::fs::read(&key_path)
+            .with_context(|| format!("Failed to read private key {}", key_path))?;
+        let key = EncodingKey::from_rsa_pem(&pem).context("Invalid GitHub App private key")?;
+
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(60))
+            .build()?;
+
+        Ok(Self {
+            client,
+            pool,
+            args,
+            app_id,
+            installation_id,
+            key,
+            token: None,
+            stats: Stats::default(),
+        })
+    }
+
+    /// A short-lived JWT identifying the app itself. GitHub allows at most
+    /// 10 minutes; `iat` is backdated to tolerate clock drift.
+    fn app_jwt(&self) -> Result<String> {
+        let now = Utc::now().timestamp();
+        let claims = AppClaims {
+            iat: now - 60,
+            exp: now + 9 * 60,
+            iss: self.app_id.clone(),
+        };
+
+        jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &self.key)
+            .context("Failed to sign GitHub App JWT")
+    }
+
+    /// The installation access token, exchanged for a fresh one shortly
+    /// before it expires (they last an hour).
+    async fn installation_token(&mut self) -> Result<String> {
+        if let Some(token) = &self.token {
+            if token.expires_at - Utc::now() > ChronoDuration::minutes(5) {
+                return Ok(token.token.clone());
+            }
+        }
+
+        let url = format!(
+            "{}/app/installations/{}/access_tokens",
+            GITHUB_API, self.installation_id
+        );
+        let token: InstallationToken = self
+            .client
+            .post(&url)
+            .bearer_auth(self.app_jwt()?)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .send()
+            .await?
+            .error_for_status()
+            .context("Failed to create installation access token")?
+            .json()
+            .await?;
+
+        debug!("Installation token valid until {}", token.expires_at);
+        let value = token.token.clone();
+        self.token = Some(token);
+        Ok(value)
+    }
+
+    /// Fetch a batch of repositories. Missing or inaccessible repositories
+    /// map to `None`.
+    async fn fetch_repositories(
+        &mut self,
+        repos: &[(String, String)],
+    ) -> Result<Vec<Option<Repository>>> {
+        let (query, variables) = build_query(repos);
+        let token = self.installation_token().await?;
+
+        let response: GraphQlResponse = self
+            .client
+            .post(format!("{}/graphql", GITHUB_API))
+            .bearer_auth(token)
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Unexpected GraphQL response")?;
+        self.stats.queries += 1;
+
+        // Unknown repositories come back as null with a NOT_FOUND error;
+        // anything else means the whole query failed
+        if let Some(error) = response
+            .errors
+            .iter()
+            .find(|error| error.kind.as_deref() != Some("NOT_FOUND"))
+        {
+            anyhow::bail!("GraphQL error: {}", error.message);
+        }
+        let mut data = response.data.context("GraphQL response has no data")?;
+
+        if let Some(Some(rate_limit)) = data.remove("rateLimit") {
+            let rate_limit: RateLimit = serde_json::from_value(rate_limit)?;
+            debug!(
+                "Query cost {}, {} points left until {}",
+                rate_limit.cost, rate_limit.remaining, rate_limit.reset_at
+            );
+            if rate_limit.remaining < rate_limit.cost {
+                let wait = (rate_limit.reset_at - Utc::now()).to_std().unwrap_or_default();
+                warn!("GraphQL rate limit exhausted, sleeping {:?}", wait);
+                sleep(wait).await;
+            }
+        }
+
+        parse_repositories(data, repos.len())
+    }
+
+    async fn update_implementations(
+        &mut self,
+        names: &[String],
+        repos: &[Repository],
+    ) -> Result<()> {
+        let stars: Vec<i32> = repos.iter().map(|r| r.stargazer_count).collect();
+        let pushed_at: Vec<Option<DateTime<Utc>>> = repos.iter().map(|r| r.pushed_at).collect();
+        let archived: Vec<bool> = repos.iter().map(|r| r.is_archived).collect();
+        let disabled: Vec<bool> = repos.iter().map(|r| r.is_disabled).collect();
+        let languages: Vec<Option<serde_json::Value>> = repos
+            .iter()
+            .map(|r| {
+                r.languages.as_ref().map(|languages| {
+                    languages
+                        .edges
+                        .iter()
+                        .map(|edge| (edge.node.name.clone(), json!(edge.size)))
+                        .collect::<serde_json::Map<_, _>>()
+                        .into()
+                })
+            })
+            .collect();
+
+        let result = sqlx::query(
+            r#"
+            UPDATE implementations
+            SET stars = batch.stars,
+                pushed_at = COALESCE(batch.pushed_at, implementations.pushed_at),
+                archived = batch.archived,
+                disabled = batch.disabled,
+                languages = COALESCE(batch.languages, implementations.languages)
+            FROM UNNEST($1::text[], $2::int[], $3::timestamptz[], $4::boolean[],
+                        $5::boolean[], $6::jsonb[])
+                AS batch(github_repo, stars, pushed_at, archived, disabled, languages)
+            WHERE implementations.github_repo = batch.github_repo
+              AND implementations.deleted_at IS NULL
+            "#,
+        )
+        .bind(names)
+        .bind(&stars)
+        .bind(&pushed_at)
+        .bind(&archived)
+        .bind(&disabled)
+        .bind(&languages)
+        .execute(&self.pool)
+        .await
+        .context("Failed to update implementations")?;
+
+        self.stats.implementations_updated += result.rows_affected();
+        Ok(())
+    }
+
+    async fn run(&mut self) -> Result<()> {
+        let batch_size = batch_size(self.args.batch_size);
+        let mut last_repo = String::new();
+
+        loop {
+            // Keyset pagination over the distinct owner/repo pairs, since
+            // several papers often link the same repository
+            let batch: Vec<String> = sqlx::query_scalar(
+                r#"
+                SELECT DISTINCT github_repo
+                FROM implementations
+                WHERE github_repo > $1
+                  AND deleted_at IS NULL
+                ORDER BY github_repo
+                LIMIT $2
+                "#,
+            )
+            .bind(&last_repo)
+            .bind(batch_size as i64)
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to fetch repositories")?;
+
+            let Some(batch_last_repo) = batch.last() else {
+                break;
+            };
+            last_repo = batch_last_repo.clone();
+
+            // github_repo is always "owner/repo", see migration 0015
+            let repos: Vec<(String, String)> = batch
+                .iter()
+                .map(|repo| repo.split_once('/').unwrap_or_default())
+                .map(|(owner, name)| (owner.to_string(), name.to_string()))
+                .collect();
+
+            match self.fetch_repositories(&repos).await {
+                Ok(results) => {
+                    let (names, found): (Vec<String>, Vec<Repository>) = batch
+                        .iter()
+                        .zip(results)
+                        .filter_map(|(name, repo)| Some((name.clone(), repo?)))
+                        .unzip();
+                    self.stats.repos_not_found += batch.len() - found.len();
+
+                    if self.args.dry_run {
+                        for (name, repo) in names.iter().zip(&found) {
+                            debug!("[DRY RUN] {}: {} stars", name, repo.stargazer_count);
+                        }
+                    } else if !found.is_empty() {
+                        self.update_implementations(&names, &found).await?;
+                    }
+                    self.stats.repos_updated += found.len();
+                }
+                Err(e) => {
+                    warn!("GraphQL query failed: {:#}", e);
+                    self.stats.errors += 1;
+                }
+            }
+            self.stats.repos_processed += batch.len();
+
+            info!(
+                "Processed {} repos ({} updated)",
+                self.stats.repos_processed, self.stats.repos_updated
+            );
+
+            if self.args.max_repos > 0 && self.stats.repos_processed >= self.args.max_repos {
+                break;
+            }
+            sleep(Duration::from_millis(self.args.delay_ms)).await;
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    let args = Args::parse();
+
+    // Setup logging
+    let log_level = if args.verbose {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(log_level)
+        .with_target(false)
+        .compact()
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    info!("Starting GitHub App sync...");
+    if args.dry_run {
+        warn!("DRY RUN MODE - No database writes will occur");
+    }
+
+    let database_url = env::var("POSTGRES_URI").context("POSTGRES_URI must be set")?;
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+    info!("Connected to database");
+
+    let started_at = Utc::now();
+    let mut sync = GitHubAppSync::new(pool, args)?;
+    sync.run().await?;
+
+    if !sync.args.dry_run {
+        let items = sync.stats.repos_updated as i64;
+        if let Err(e) = record_ingestion_run(&sync.pool, "github", started_at, items).await {
+            warn!("Failed to record ingestion run: {}", e);
+        }
+    }
+
+    info!("=== GitHub App Sync Statistics ===");
+    info!("Repos processed:         {}", sync.stats.repos_processed);
+    info!("Repos updated:           {}", sync.stats.repos_updated);
+    info!("Implementations updated: {}", sync.stats.implementations_updated);
+    info!("Repos not found:         {}", sync.stats.repos_not_found);
+    info!("GraphQL queries:         {}", sync.stats.queries);
+    info!("Errors:                  {}", sync.stats.errors);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repos(names: &[&str]) -> Vec<(String, String)> {
+        names
+            .iter()
+            .map(|repo| repo.split_once('/').unwrap())
+            .map(|(owner, name)| (owner.to_string(), name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn query_aliases_each_repository() {
+        let (query, variables) = build_query(&repos(&["openai/CLIP", "facebookresearch/detr"]));
+
+        assert!(query.starts_with("query($o0: String!, $n0: String!, $o1: String!, $n1: String!)"));
+        assert!(query.contains("r0: repository(owner: $o0, name: $n0) { ...RepositoryFields }"));
+        assert!(query.contains("r1: repository(owner: $o1, name: $n1) { ...RepositoryFields }"));
+        assert!(query.contains("rateLimit { cost remaining resetAt }"));
+        assert!(query.ends_with(REPOSITORY_FIELDS));
+        assert_eq!(
+            variables,
+            json!({
+                "o0": "openai",
+                "n0": "CLIP",
+                "o1": "facebookresearch",
+                "n1": "detr",
+            })
+        );
+    }
+
+    #[test]
+    fn repository_names_stay_out_of_the_query_text() {
+        let name = r#"repo") { id } evil: viewer { login } x: repository(name: "#;
+        let owner = r#"quo"te\slash"#;
+        let (query, variables) = build_query(&[(owner.to_string(), name.to_string())]);
+
+        assert!(!query.contains("evil"));
+        assert!(!query.contains("quo"));
+        assert_eq!(variables["o0"], owner);
+        assert_eq!(variables["n0"], name);
+
+        // The variables serialize as ordinary JSON strings
+        let body = serde_json::to_string(&variables).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(parsed["o0"], owner);
+        assert_eq!(parsed["n0"], name);
+    }
+
+    #[test]
+    fn batches_are_capped_at_the_query_limit() {
+        assert_eq!(batch_size(0), 1);
+        assert_eq!(batch_size(50), 50);
+        assert_eq!(batch_size(MAX_REPOS_PER_QUERY), MAX_REPOS_PER_QUERY);
+        assert_eq!(batch_size(1000), MAX_REPOS_PER_QUERY);
+
+        let names: Vec<String> = (0..batch_size(1000)).map(|i| format!("owner/repo{i}")).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let (query, variables) = build_query(&repos(&names));
+
+        assert_eq!(query.matches(": repository(").count(), MAX_REPOS_PER_QUERY);
+        assert!(query.contains("r99: repository(owner: $o99, name: $n99)"));
+        assert!(!query.contains("r100:"));
+        assert_eq!(variables.as_object().unwrap().len(), 2 * MAX_REPOS_PER_QUERY);
+    }
+
+    #[test]
+    fn responses_map_back_to_their_repositories() {
+        let repository = |stars: i32| {
+            Some(json!({
+                "stargazerCount": stars,
+                "pushedAt": "2024-05-01T12:00:00Z",
+                "isArchived": false,
+                "isDisabled": false,
+                "languages": { "edges": [{ "size": 1200, "node": { "name": "Python" } }] },
+            }))
+        };
+        // r1 was not found and r3 is missing from the response entirely
+        let data = HashMap::from([
+            ("r2".to_string(), repository(30)),
+            ("r0".to_string(), repository(10)),
+            ("r1".to_string(), None),
+        ]);
+
+        let results = parse_repositories(data, 4).unwrap();
+
+        let stars: Vec<Option<i32>> = results
+            .iter()
+            .map(|repo| repo.as_ref().map(|repo| repo.stargazer_count))
+            .collect();
+        assert_eq!(stars, [Some(10), None, Some(30), None]);
+
+        let languages = &results[0].as_ref().unwrap().languages.as_ref().unwrap().edges;
+        assert_eq!(languages[0].node.name, "Python");
+        assert_eq!(languages[0].size, 1200);
+    }
+
+    #[test]
+    fn malformed_repositories_are_errors() {
+        let data = HashMap::from([("r0".to_string(), Some(json!({ "stargazerCount": "many" })))]);
+
+        assert!(parse_repositories(data, 1).is_err());
+    }
+}
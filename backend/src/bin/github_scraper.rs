@@ -2,14 +2,21 @@
 //!
 //! This scraper fetches GitHub API data for repositories linked to papers
 //! and updates the implementations table with stars, forks, and other metadata.
+//! It also inspects the repository root for reproducibility artifacts
+//! (Dockerfiles, conda environment files, pinned requirements) and for
+//! packages published to PyPI, crates.io, or npm, records the repository's
+//! language byte breakdown, and extracts declared Python dependencies.
 
 use anyhow::{Context, Result};
+use backend::{normalize_package_name, record_ingestion_run};
 use clap::Parser;
 use dotenvy::dotenv;
 use serde::Deserialize;
 use sqlx::postgres::{PgPool, PgPoolOptions};
 use sqlx::Row;
+use regex::Regex;
 use std::env;
+use std::sync::LazyLock;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn, Level};
@@ -17,6 +24,20 @@ use tracing_subscriber::FmtSubscriber;
 
 const USER_AGENT: &str = "CodeWithPapers-Replicator/1.0 (Educational/Research Purpose; https://github.com/GeorgePearse/codewithpapers)";
 
+/// Name, optional `[extras]` and the remainder of a PEP 508 requirement
+static REQUIREMENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([A-Za-z0-9][A-Za-z0-9._-]*)\s*(\[[^\]]*\])?\s*(.*)$").unwrap()
+});
+static QUOTED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""([^"]*)"|'([^']*)'"#).unwrap());
+static POETRY_VERSION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"version\s*=\s*["']([^"']+)["']"#).unwrap());
+static SETUP_PY_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bname\s*=\s*["']([^"']+)["']"#).unwrap());
+
+/// pip VCS URL schemes, e.g. `git+https://...` or `git+git@github.com:...`
+const VCS_PREFIXES: &[&str] = &["git+", "hg+", "svn+", "bzr+"];
+
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Scrape GitHub stats for paper implementations", long_about = None)]
 struct Args {
@@ -45,6 +66,8 @@ struct Args {
     verbose: bool,
 }
 
+// Not every field is persisted yet; the rest are kept for debugging output.
+#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 struct GitHubRepo {
     stargazers_count: i32,
@@ -55,10 +78,243 @@ struct GitHubRepo {
     description: Option<String>,
     archived: bool,
     disabled: bool,
-    pushed_at: Option<String>,
+    pushed_at: Option<chrono::DateTime<chrono::Utc>>,
     topics: Option<Vec<String>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct GitHubContent {
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+/// Reproducibility artifacts found in a repository root.
+#[derive(Debug, Default)]
+struct ReproducibilityFlags {
+    has_dockerfile: bool,
+    has_conda_env: bool,
+    has_pinned_requirements: bool,
+}
+
+impl ReproducibilityFlags {
+    fn any(&self) -> bool {
+        self.has_dockerfile || self.has_conda_env || self.has_pinned_requirements
+    }
+}
+
+/// Lock files that pin the full Python dependency tree.
+const PYTHON_LOCK_FILES: &[&str] = &[
+    "poetry.lock",
+    "Pipfile.lock",
+    "uv.lock",
+    "pdm.lock",
+    "requirements.lock",
+];
+
+/// Conda environment definitions.
+const CONDA_ENV_FILES: &[&str] = &[
+    "environment.yml",
+    "environment.yaml",
+    "conda.yml",
+    "conda.yaml",
+    "conda-lock.yml",
+];
+
+fn is_dockerfile(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    lower == "dockerfile"
+        || lower.starts_with("dockerfile.")
+        || lower.ends_with(".dockerfile")
+        || lower == "docker-compose.yml"
+        || lower == "docker-compose.yaml"
+}
+
+/// A requirements file is considered pinned when every requirement line
+/// specifies an exact version (`==`/`===`) or a direct reference (`@`).
+fn requirements_are_pinned(content: &str) -> bool {
+    let requirements: Vec<&str> = content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('-'))
+        .collect();
+
+    !requirements.is_empty()
+        && requirements
+            .iter()
+            .all(|line| line.contains("==") || line.contains(" @ "))
+}
+
+/// A package published from the repository to a public registry.
+#[derive(Debug)]
+struct DetectedPackage {
+    registry: &'static str,
+    name: String,
+    url: String,
+}
+
+/// A direct dependency declared in a repository's Python manifests.
+#[derive(Debug)]
+struct DetectedDependency {
+    /// PEP 503 normalized name
+    name: String,
+    version_spec: Option<String>,
+    source: &'static str,
+}
+
+/// Everything learned from inspecting a repository beyond its basic stats.
+#[derive(Debug, Default)]
+struct RepoInspection {
+    flags: ReproducibilityFlags,
+    packages: Vec<DetectedPackage>,
+    /// Bytes of code per language, as reported by GitHub
+    languages: Option<serde_json::Value>,
+    dependencies: Vec<DetectedDependency>,
+}
+
+/// Parse a PEP 508 requirement such as `torch[cuda]>=2.0; python_version > "3.8"`
+/// into its name and version specifier. Direct references (`name @ url`) keep
+/// only the name; pip options, paths, URLs and VCS references have none.
+fn parse_requirement(requirement: &str) -> Option<(String, Option<String>)> {
+    let requirement = requirement.split(';').next().unwrap_or("").trim();
+    if requirement.starts_with('-') || VCS_PREFIXES.iter().any(|p| requirement.starts_with(p)) {
+        return None;
+    }
+    let caps = REQUIREMENT.captures(requirement)?;
+
+    let spec = caps[3].trim();
+    if spec.starts_with('@') {
+        return Some((caps[1].to_string(), None));
+    }
+    // URLs and local paths
+    if spec.contains('/') {
+        return None;
+    }
+    let spec = if spec.is_empty() {
+        None
+    } else {
+        Some(spec.to_string())
+    };
+    Some((caps[1].to_string(), spec))
+}
+
+/// Dependencies listed in a `requirements.txt`, skipping pip options and includes.
+fn requirements_txt_dependencies(content: &str) -> Vec<(String, Option<String>)> {
+    content
+        .lines()
+        .map(|line| line.split(" #").next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(parse_requirement)
+        .collect()
+}
+
+/// Dependencies from a `pyproject.toml`, covering both PEP 621
+/// `[project] dependencies` and `[tool.poetry.dependencies]`.
+fn pyproject_dependencies(content: &str) -> Vec<(String, Option<String>)> {
+    let quoted_strings = |text: &str| -> Vec<String> {
+        QUOTED
+            .captures_iter(text)
+            .filter_map(|c| c.get(1).or_else(|| c.get(2)).map(|m| m.as_str().to_string()))
+            .collect()
+    };
+
+    let mut dependencies = Vec::new();
+    let mut section = String::new();
+    let mut in_array = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+
+        if in_array {
+            dependencies.extend(quoted_strings(line).iter().filter_map(|r| parse_requirement(r)));
+            in_array = !QUOTED.replace_all(line, "").contains(']');
+            continue;
+        }
+
+        if line.starts_with('[') {
+            section = line.trim_matches(|c| c == '[' || c == ']').trim().to_string();
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().trim_matches('"');
+
+        if section == "project" && key == "dependencies" {
+            dependencies.extend(quoted_strings(value).iter().filter_map(|r| parse_requirement(r)));
+            in_array = !QUOTED.replace_all(value, "").contains(']');
+        } else if section == "tool.poetry.dependencies" && key != "python" {
+            let value = value.trim();
+            let spec = if value.starts_with('{') {
+                POETRY_VERSION.captures(value).map(|c| c[1].to_string())
+            } else {
+                quoted_strings(value).into_iter().next()
+            };
+            dependencies.push((key.to_string(), spec));
+        }
+    }
+
+    dependencies
+}
+
+/// Find `key = "value"` inside a `[section]` of a TOML file.
+fn toml_section_value(content: &str, section: &str, key: &str) -> Option<String> {
+    let pattern = Regex::new(&format!(r#"^{}\s*=\s*["']([^"']+)["']"#, regex::escape(key))).ok()?;
+    let header = format!("[{}]", section);
+    let mut in_section = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_section = line == header;
+            continue;
+        }
+        if in_section {
+            if let Some(caps) = pattern.captures(line) {
+                return Some(caps[1].to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Extract the distribution name from a `setup.py` `setup(name=...)` call.
+fn setup_py_name(content: &str) -> Option<String> {
+    SETUP_PY_NAME.captures(content).map(|caps| caps[1].to_string())
+}
+
+/// Extract the package name from a `package.json`, skipping private packages.
+fn package_json_name(content: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(content).ok()?;
+    if value.get("private").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return None;
+    }
+    value.get("name")?.as_str().map(|s| s.to_string())
+}
+
+/// Check whether registry metadata links back to the given GitHub repository.
+fn metadata_links_repo(metadata: &serde_json::Value, pointers: &[&str], owner: &str, repo: &str) -> bool {
+    let needle = format!("github.com/{}/{}", owner, repo).to_lowercase();
+    let mentions = |v: &serde_json::Value| {
+        v.as_str()
+            .map(|s| {
+                let s = s.to_lowercase();
+                let s = s.trim_end_matches('/').trim_end_matches(".git");
+                s.ends_with(&needle) || s.contains(&format!("{}/", needle))
+            })
+            .unwrap_or(false)
+    };
+
+    pointers.iter().filter_map(|p| metadata.pointer(p)).any(|v| match v {
+        serde_json::Value::Object(map) => map.values().any(mentions),
+        other => mentions(other),
+    })
+}
+
 #[derive(Debug)]
 struct Implementation {
     id: uuid::Uuid,
@@ -71,12 +327,16 @@ struct ScraperStats {
     repos_processed: usize,
     repos_updated: usize,
     repos_not_found: usize,
+    repos_reproducible: usize,
+    packages_found: usize,
     rate_limited: usize,
     errors: usize,
 }
 
 struct GitHubScraper {
     client: reqwest::Client,
+    /// Unauthenticated client for package registries (never sends the GitHub token)
+    registry_client: reqwest::Client,
     pool: Option<PgPool>,
     delay: Duration,
     dry_run: bool,
@@ -113,8 +373,15 @@ impl GitHubScraper {
             .build()
             .context("Failed to create HTTP client")?;
 
+        let registry_client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build()
+            .context("Failed to create HTTP client")?;
+
         Ok(Self {
             client,
+            registry_client,
             pool,
             delay: Duration::from_millis(delay_ms),
             dry_run,
@@ -167,6 +434,270 @@ impl GitHubScraper {
         Ok(Some(repo_data))
     }
 
+    async fn fetch_root_contents(&self, owner: &str, repo: &str) -> Result<Vec<GitHubContent>> {
+        let url = format!("https://api.github.com/repos/{}/{}/contents/", owner, repo);
+        debug!("Fetching: {}", url);
+
+        sleep(self.delay).await;
+
+        let resp = self.client.get(&url).send().await?;
+        let status = resp.status();
+
+        // Empty repositories have no contents
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+
+        if status == reqwest::StatusCode::FORBIDDEN || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            warn!("Rate limited by GitHub API");
+            return Err(anyhow::anyhow!("Rate limited"));
+        }
+
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("HTTP {} for {}", status, url));
+        }
+
+        Ok(resp.json().await?)
+    }
+
+    async fn fetch_languages(&self, owner: &str, repo: &str) -> Result<Option<serde_json::Value>> {
+        let url = format!("https://api.github.com/repos/{}/{}/languages", owner, repo);
+        debug!("Fetching: {}", url);
+
+        sleep(self.delay).await;
+
+        let resp = self.client.get(&url).send().await?;
+        let status = resp.status();
+
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if status == reqwest::StatusCode::FORBIDDEN || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            warn!("Rate limited by GitHub API");
+            return Err(anyhow::anyhow!("Rate limited"));
+        }
+
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("HTTP {} for {}", status, url));
+        }
+
+        Ok(Some(resp.json().await?))
+    }
+
+    async fn fetch_file(&self, owner: &str, repo: &str, path: &str) -> Result<Option<String>> {
+        let url = format!("https://api.github.com/repos/{}/{}/contents/{}", owner, repo, path);
+        debug!("Fetching: {}", url);
+
+        sleep(self.delay).await;
+
+        let resp = self
+            .client
+            .get(&url)
+            .header(reqwest::header::ACCEPT, "application/vnd.github.raw")
+            .send()
+            .await?;
+        let status = resp.status();
+
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if status == reqwest::StatusCode::FORBIDDEN || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            warn!("Rate limited by GitHub API");
+            return Err(anyhow::anyhow!("Rate limited"));
+        }
+
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("HTTP {} for {}", status, url));
+        }
+
+        Ok(Some(resp.text().await?))
+    }
+
+    /// Fetch registry metadata, returning `None` if the package does not exist.
+    async fn fetch_registry_metadata(&self, url: &str) -> Result<Option<serde_json::Value>> {
+        debug!("Fetching: {}", url);
+
+        sleep(self.delay).await;
+
+        let resp = self.registry_client.get(url).send().await?;
+        let status = resp.status();
+
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("HTTP {} for {}", status, url));
+        }
+
+        Ok(Some(resp.json().await?))
+    }
+
+    /// Confirm a declared package exists on its registry and points back at this repo.
+    async fn verify_package(
+        &self,
+        registry: &'static str,
+        name: &str,
+        owner: &str,
+        repo: &str,
+    ) -> Option<DetectedPackage> {
+        let (api_url, pointers, url): (String, &[&str], String) = match registry {
+            "pypi" => (
+                format!("https://pypi.org/pypi/{}/json", name),
+                &["/info/home_page", "/info/project_urls"],
+                format!("https://pypi.org/project/{}/", name),
+            ),
+            "crates" => (
+                format!("https://crates.io/api/v1/crates/{}", name),
+                &["/crate/repository", "/crate/homepage"],
+                format!("https://crates.io/crates/{}", name),
+            ),
+            "npm" => (
+                format!("https://registry.npmjs.org/{}", name),
+                &["/repository/url", "/repository", "/homepage"],
+                format!("https://www.npmjs.com/package/{}", name),
+            ),
+            _ => return None,
+        };
+
+        match self.fetch_registry_metadata(&api_url).await {
+            Ok(Some(metadata)) if metadata_links_repo(&metadata, pointers, owner, repo) => {
+                Some(DetectedPackage {
+                    registry,
+                    name: name.to_string(),
+                    url,
+                })
+            }
+            Ok(_) => None,
+            Err(e) => {
+                debug!("Registry lookup failed for {} package {}: {}", registry, name, e);
+                None
+            }
+        }
+    }
+
+    async fn detect_packages(
+        &self,
+        owner: &str,
+        repo: &str,
+        files: &[&str],
+        pyproject_toml: Option<&str>,
+    ) -> Result<Vec<DetectedPackage>> {
+        let mut candidates: Vec<(&'static str, String)> = Vec::new();
+
+        if let Some(content) = pyproject_toml {
+            if let Some(name) = toml_section_value(content, "project", "name")
+                .or_else(|| toml_section_value(content, "tool.poetry", "name"))
+            {
+                candidates.push(("pypi", name));
+            }
+        }
+
+        if !candidates.iter().any(|(r, _)| *r == "pypi") && files.contains(&"setup.py") {
+            if let Some(content) = self.fetch_file(owner, repo, "setup.py").await? {
+                if let Some(name) = setup_py_name(&content) {
+                    candidates.push(("pypi", name));
+                }
+            }
+        }
+
+        if files.contains(&"Cargo.toml") {
+            if let Some(content) = self.fetch_file(owner, repo, "Cargo.toml").await? {
+                if let Some(name) = toml_section_value(&content, "package", "name") {
+                    candidates.push(("crates", name));
+                }
+            }
+        }
+
+        if files.contains(&"package.json") {
+            if let Some(content) = self.fetch_file(owner, repo, "package.json").await? {
+                if let Some(name) = package_json_name(&content) {
+                    candidates.push(("npm", name));
+                }
+            }
+        }
+
+        let mut packages = Vec::new();
+        for (registry, name) in candidates {
+            if let Some(package) = self.verify_package(registry, &name, owner, repo).await {
+                packages.push(package);
+            }
+        }
+
+        Ok(packages)
+    }
+
+    async fn inspect_repo(&self, owner: &str, repo: &str) -> Result<RepoInspection> {
+        let contents = self.fetch_root_contents(owner, repo).await?;
+        let files: Vec<&str> = contents
+            .iter()
+            .filter(|c| c.kind == "file")
+            .map(|c| c.name.as_str())
+            .collect();
+
+        let mut flags = ReproducibilityFlags {
+            has_dockerfile: files.iter().any(|f| is_dockerfile(f)),
+            has_conda_env: files.iter().any(|f| CONDA_ENV_FILES.contains(f)),
+            has_pinned_requirements: files.iter().any(|f| PYTHON_LOCK_FILES.contains(f)),
+        };
+
+        let requirements_txt = if files.contains(&"requirements.txt") {
+            self.fetch_file(owner, repo, "requirements.txt").await?
+        } else {
+            None
+        };
+        let pyproject_toml = if files.contains(&"pyproject.toml") {
+            self.fetch_file(owner, repo, "pyproject.toml").await?
+        } else {
+            None
+        };
+
+        if let Some(ref content) = requirements_txt {
+            flags.has_pinned_requirements |= requirements_are_pinned(content);
+        }
+
+        // pyproject.toml takes precedence; requirements.txt fills in the rest
+        let mut dependencies: Vec<DetectedDependency> = Vec::new();
+        let declared = pyproject_toml
+            .as_deref()
+            .map(pyproject_dependencies)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|dep| (dep, "pyproject.toml"))
+            .chain(
+                requirements_txt
+                    .as_deref()
+                    .map(requirements_txt_dependencies)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|dep| (dep, "requirements.txt")),
+            );
+        for ((name, version_spec), source) in declared {
+            let name = normalize_package_name(&name);
+            if !dependencies.iter().any(|d| d.name == name) {
+                dependencies.push(DetectedDependency {
+                    name,
+                    version_spec,
+                    source,
+                });
+            }
+        }
+
+        let packages = self
+            .detect_packages(owner, repo, &files, pyproject_toml.as_deref())
+            .await?;
+        let languages = self.fetch_languages(owner, repo).await?;
+
+        Ok(RepoInspection {
+            flags,
+            packages,
+            languages,
+            dependencies,
+        })
+    }
+
     async fn get_implementations(&self, pool: &PgPool, limit: usize) -> Result<Vec<Implementation>> {
         let query = if limit > 0 {
             sqlx::query(
@@ -203,24 +734,41 @@ impl GitHubScraper {
         Ok(implementations)
     }
 
+    /// Update stats and repository flags. Without an inspection (it failed),
+    /// the reproducibility flags and languages keep their previous values.
     async fn update_implementation(
         &self,
         pool: &PgPool,
         impl_id: uuid::Uuid,
         repo: &GitHubRepo,
         framework: Option<&str>,
+        inspection: Option<&RepoInspection>,
     ) -> Result<()> {
+        let flags = inspection.map(|inspection| &inspection.flags);
         sqlx::query(
             r#"
             UPDATE implementations
             SET stars = $1,
                 framework = COALESCE($2, framework),
-                updated_at = NOW()
-            WHERE id = $3
+                has_dockerfile = COALESCE($3, has_dockerfile),
+                has_conda_env = COALESCE($4, has_conda_env),
+                has_pinned_requirements = COALESCE($5, has_pinned_requirements),
+                languages = COALESCE($6, languages),
+                archived = $7,
+                disabled = $8,
+                pushed_at = COALESCE($9, pushed_at)
+            WHERE id = $10
             "#,
         )
         .bind(repo.stargazers_count)
         .bind(framework)
+        .bind(flags.map(|flags| flags.has_dockerfile))
+        .bind(flags.map(|flags| flags.has_conda_env))
+        .bind(flags.map(|flags| flags.has_pinned_requirements))
+        .bind(inspection.and_then(|inspection| inspection.languages.as_ref()))
+        .bind(repo.archived)
+        .bind(repo.disabled)
+        .bind(repo.pushed_at)
         .bind(impl_id)
         .execute(pool)
         .await?;
@@ -228,6 +776,105 @@ impl GitHubScraper {
         Ok(())
     }
 
+    async fn save_inspection(
+        &self,
+        pool: &PgPool,
+        impl_id: uuid::Uuid,
+        repo: &GitHubRepo,
+        framework: Option<&str>,
+        inspection: Option<&RepoInspection>,
+    ) -> Result<()> {
+        self.update_implementation(pool, impl_id, repo, framework, inspection)
+            .await?;
+        if let Some(inspection) = inspection {
+            self.update_packages(pool, impl_id, &inspection.packages)
+                .await?;
+            self.update_dependencies(pool, impl_id, &inspection.dependencies)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn update_dependencies(
+        &self,
+        pool: &PgPool,
+        impl_id: uuid::Uuid,
+        dependencies: &[DetectedDependency],
+    ) -> Result<()> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query("DELETE FROM implementation_dependencies WHERE implementation_id = $1")
+            .bind(impl_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let names: Vec<&str> = dependencies.iter().map(|d| d.name.as_str()).collect();
+        let specs: Vec<Option<&str>> = dependencies.iter().map(|d| d.version_spec.as_deref()).collect();
+        let sources: Vec<&str> = dependencies.iter().map(|d| d.source).collect();
+
+        sqlx::query(
+            r#"
+            INSERT INTO implementation_dependencies (implementation_id, name, version_spec, source)
+            SELECT $1, * FROM UNNEST($2::text[], $3::text[], $4::text[])
+            "#,
+        )
+        .bind(impl_id)
+        .bind(&names)
+        .bind(&specs)
+        .bind(&sources)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update_packages(
+        &self,
+        pool: &PgPool,
+        impl_id: uuid::Uuid,
+        packages: &[DetectedPackage],
+    ) -> Result<()> {
+        let mut tx = pool.begin().await?;
+
+        let keys: Vec<String> = packages
+            .iter()
+            .map(|p| format!("{}:{}", p.registry, p.name))
+            .collect();
+
+        // Drop packages that are no longer declared by the repository
+        sqlx::query(
+            r#"
+            DELETE FROM packages
+            WHERE implementation_id = $1 AND NOT (registry || ':' || name = ANY($2))
+            "#,
+        )
+        .bind(impl_id)
+        .bind(&keys)
+        .execute(&mut *tx)
+        .await?;
+
+        for package in packages {
+            sqlx::query(
+                r#"
+                INSERT INTO packages (implementation_id, registry, name, url)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (implementation_id, registry, name) DO UPDATE SET
+                    url = EXCLUDED.url
+                "#,
+            )
+            .bind(impl_id)
+            .bind(package.registry)
+            .bind(&package.name)
+            .bind(&package.url)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
     async fn run(&mut self, max_repos: usize) -> Result<()> {
         let pool = match &self.pool {
             Some(p) => p,
@@ -247,9 +894,41 @@ impl GitHubScraper {
                     Ok(Some(repo_data)) => {
                         let framework = repo_data.language.as_deref();
 
+                        // Stars and repository flags are still saved if the
+                        // inspection fails; only its own fields are skipped
+                        let mut rate_limited = false;
+                        let inspection = match self.inspect_repo(&owner, &repo).await {
+                            Ok(inspection) => Some(inspection),
+                            Err(e) => {
+                                if e.to_string().contains("Rate limited") {
+                                    self.stats.rate_limited += 1;
+                                    rate_limited = true;
+                                } else {
+                                    error!("Error inspecting {}/{}: {}", owner, repo, e);
+                                    self.stats.errors += 1;
+                                }
+                                None
+                            }
+                        };
+                        if let Some(inspection) = &inspection {
+                            if inspection.flags.any() {
+                                self.stats.repos_reproducible += 1;
+                            }
+                            self.stats.packages_found += inspection.packages.len();
+                        }
+
                         if !self.dry_run {
                             if let Some(pool) = &self.pool {
-                                match self.update_implementation(pool, imp.id, &repo_data, framework).await {
+                                match self
+                                    .save_inspection(
+                                        pool,
+                                        imp.id,
+                                        &repo_data,
+                                        framework,
+                                        inspection.as_ref(),
+                                    )
+                                    .await
+                                {
                                     Ok(_) => {
                                         debug!(
                                             "Updated {}/{}: {} stars",
@@ -265,12 +944,17 @@ impl GitHubScraper {
                             }
                         } else {
                             debug!(
-                                "[DRY RUN] Would update {}/{}: {} stars, lang: {:?}",
-                                owner, repo, repo_data.stargazers_count, framework
+                                "[DRY RUN] Would update {}/{}: {} stars, lang: {:?}, {:?}",
+                                owner, repo, repo_data.stargazers_count, framework, inspection
                             );
                             self.stats.repos_updated += 1;
                         }
                         self.stats.repos_processed += 1;
+
+                        if rate_limited {
+                            warn!("Rate limited - stopping scraper");
+                            break;
+                        }
                     }
                     Ok(None) => {
                         self.stats.repos_not_found += 1;
@@ -301,6 +985,8 @@ impl GitHubScraper {
         info!("Repos processed: {}", self.stats.repos_processed);
         info!("Repos updated: {}", self.stats.repos_updated);
         info!("Repos not found (404): {}", self.stats.repos_not_found);
+        info!("Repos with reproducibility artifacts: {}", self.stats.repos_reproducible);
+        info!("Registry packages found: {}", self.stats.packages_found);
         info!("Rate limited: {}", self.stats.rate_limited);
         info!("Errors: {}", self.stats.errors);
     }
@@ -348,10 +1034,81 @@ async fn main() -> Result<()> {
         Some(pool)
     };
 
+    let started_at = chrono::Utc::now();
     let mut scraper = GitHubScraper::new(pool, args.delay_ms, args.dry_run, token).await?;
     scraper.run(args.max_repos).await?;
     scraper.print_stats();
 
+    if let Some(pool) = &scraper.pool {
+        let items = scraper.stats.repos_updated as i64;
+        if let Err(e) = record_ingestion_run(pool, "github", started_at, items).await {
+            warn!("Failed to record ingestion run: {}", e);
+        }
+    }
+
     info!("GitHub scraping complete.");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requirement(name: &str, spec: Option<&str>) -> Option<(String, Option<String>)> {
+        Some((name.to_string(), spec.map(str::to_string)))
+    }
+
+    #[test]
+    fn requirements_are_parsed() {
+        assert_eq!(
+            parse_requirement(r#"torch[cuda]>=2.0; python_version > "3.8""#),
+            requirement("torch", Some(">=2.0"))
+        );
+        assert_eq!(parse_requirement("numpy"), requirement("numpy", None));
+        assert_eq!(
+            parse_requirement("clip @ git+https://github.com/openai/CLIP.git"),
+            requirement("clip", None)
+        );
+        assert_eq!(
+            parse_requirement("detectron2[all] @ https://example.org/detectron2-0.6.tar.gz"),
+            requirement("detectron2", None)
+        );
+    }
+
+    #[test]
+    fn requirements_without_a_registry_name_are_skipped() {
+        for line in [
+            "-e git+https://github.com/org/repo.git#egg=repo",
+            "-e .",
+            "--extra-index-url https://download.pytorch.org/whl/cu118",
+            "git+https://github.com/openai/CLIP.git",
+            "git+git@github.com:org/repo.git",
+            "hg+https://hg.example.org/repo",
+            "https://example.org/package-1.0.tar.gz",
+            "file:///home/user/package",
+            "vendor/package",
+        ] {
+            assert_eq!(parse_requirement(line), None, "{}", line);
+        }
+    }
+
+    #[test]
+    fn requirements_txt_lines_are_filtered() {
+        let content = "\
+# Core
+torch==2.1.0  # pinned for CUDA 11.8
+-r base.txt
+--index-url https://download.pytorch.org/whl/cu118
+-e git+https://github.com/org/repo.git#egg=repo
+git+https://github.com/openai/CLIP.git
+segment-anything @ git+https://github.com/facebookresearch/segment-anything.git
+";
+        assert_eq!(
+            requirements_txt_dependencies(content),
+            [
+                ("torch".to_string(), Some("==2.1.0".to_string())),
+                ("segment-anything".to_string(), None),
+            ]
+        );
+    }
+}
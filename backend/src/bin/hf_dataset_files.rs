@@ -0,0 +1,374 @@
+//! Hugging Face Dataset Files - Records download URLs and checksums
+//!
+//! For each dataset whose homepage is a Hugging Face dataset repository, lists
+//! the repository's data files through the Hub API and stores their download
+//! URL, format, size and SHA-256 in `dataset_files`. Checksums come from Git
+//! LFS, so small files stored directly in Git are recorded without one. Files
+//! that have disappeared from the repository are removed.
+//!
+//! Usage:
+//!     hf_dataset_files
+//!     hf_dataset_files --max-datasets 100 --dry-run
+
+use anyhow::{Context, Result};
+use backend::record_ingestion_run;
+use clap::Parser;
+use dotenvy::dotenv;
+use serde::Deserialize;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::env;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{debug, info, warn, Level};
+use tracing_subscriber::FmtSubscriber;
+
+const HF_HUB: &str = "https://huggingface.co";
+const USER_AGENT: &str = "CodeWithPapers/1.0 (https://github.com/GeorgePearse/codewithpapers)";
+
+/// File extensions recorded as dataset files, longest first so compressed
+/// formats like `jsonl.gz` win over `gz`
+const DATA_FORMATS: &[&str] = &[
+    "jsonl.gz", "json.gz", "csv.gz", "tsv.gz", "txt.gz", "tar.gz", "tar.zst", "parquet", "arrow",
+    "jsonl", "json", "csv", "tsv", "txt", "zip", "tar", "tgz", "gz", "zst", "7z", "h5", "hdf5",
+    "npz", "npy",
+];
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Record Hugging Face dataset files and checksums", long_about = None)]
+struct Args {
+    /// Maximum number of datasets to process (0 = all)
+    #[arg(short, long, default_value_t = 0)]
+    max_datasets: i64,
+
+    /// Skip repositories with more data files than this
+    #[arg(long, default_value_t = 1000)]
+    max_files: usize,
+
+    /// Git revision to list and link files at
+    #[arg(long, default_value = "main")]
+    revision: String,
+
+    /// Delay between Hub API requests in milliseconds
+    #[arg(short, long, default_value_t = 200)]
+    delay_ms: u64,
+
+    /// Dry run - don't write to database
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Verbose output
+    #[arg(short, long, default_value_t = false)]
+    verbose: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TreeEntry {
+    #[serde(rename = "type")]
+    kind: String,
+    path: String,
+    #[serde(default)]
+    size: Option<i64>,
+    #[serde(default)]
+    lfs: Option<LfsPointer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LfsPointer {
+    /// SHA-256 of the file contents
+    oid: String,
+    size: i64,
+}
+
+#[derive(Debug)]
+struct DataFile {
+    url: String,
+    format: String,
+    size_bytes: Option<i64>,
+    sha256: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct Stats {
+    datasets_processed: usize,
+    datasets_skipped: usize,
+    files_recorded: usize,
+    files_removed: u64,
+    errors: usize,
+}
+
+/// Repository ID (`owner/name`, or `name` for legacy canonical datasets) from
+/// a `https://huggingface.co/datasets/...` URL.
+fn hf_repo_id(url: &str) -> Option<String> {
+    let path = url
+        .trim()
+        .strip_prefix("https://huggingface.co/datasets/")
+        .or_else(|| url.trim().strip_prefix("http://huggingface.co/datasets/"))?;
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    match segments.as_slice() {
+        [] => None,
+        // A third segment is a page like tree/main or viewer
+        [name] | [name, "tree" | "blob" | "viewer", ..] => Some(name.to_string()),
+        [owner, name, ..] => Some(format!("{}/{}", owner, name)),
+    }
+}
+
+fn data_format(path: &str) -> Option<&'static str> {
+    let filename = path.rsplit('/').next()?.to_lowercase();
+    DATA_FORMATS
+        .iter()
+        .find(|format| {
+            filename
+                .strip_suffix(*format)
+                .is_some_and(|stem| stem.len() > 1 && stem.ends_with('.'))
+        })
+        .copied()
+}
+
+/// Target of the `rel="next"` entry in a `Link` header.
+fn next_link(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let link = headers.get(reqwest::header::LINK)?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (target, params) = part.split_once(';')?;
+        params
+            .contains("rel=\"next\"")
+            .then(|| target.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
+
+struct DatasetFilesScraper {
+    client: reqwest::Client,
+    pool: PgPool,
+    token: Option<String>,
+    args: Args,
+    stats: Stats,
+}
+
+impl DatasetFilesScraper {
+    fn new(pool: PgPool, args: Args) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build()?;
+
+        // Only needed for gated or private repositories
+        let token = env::var("HF_TOKEN").ok().filter(|token| !token.is_empty());
+
+        Ok(Self {
+            client,
+            pool,
+            token,
+            args,
+            stats: Stats::default(),
+        })
+    }
+
+    /// Data files in a repository, following the tree listing's pagination.
+    async fn list_files(&self, repo_id: &str) -> Result<Vec<DataFile>> {
+        let mut url = format!(
+            "{}/api/datasets/{}/tree/{}?recursive=true",
+            HF_HUB, repo_id, self.args.revision
+        );
+        let mut files = Vec::new();
+
+        loop {
+            let mut request = self.client.get(&url);
+            if let Some(token) = &self.token {
+                request = request.bearer_auth(token);
+            }
+            let response = request.send().await?.error_for_status()?;
+            let next = next_link(response.headers());
+            let entries: Vec<TreeEntry> = response
+                .json()
+                .await
+                .with_context(|| format!("Unexpected response from {}", url))?;
+
+            for entry in entries.into_iter().filter(|entry| entry.kind == "file") {
+                let Some(format) = data_format(&entry.path) else {
+                    continue;
+                };
+                files.push(DataFile {
+                    url: format!(
+                        "{}/datasets/{}/resolve/{}/{}",
+                        HF_HUB, repo_id, self.args.revision, entry.path
+                    ),
+                    format: format.to_string(),
+                    size_bytes: entry.lfs.as_ref().map(|lfs| lfs.size).or(entry.size),
+                    sha256: entry.lfs.map(|lfs| lfs.oid.to_lowercase()),
+                });
+            }
+
+            sleep(Duration::from_millis(self.args.delay_ms)).await;
+            match next {
+                Some(next) => url = next,
+                None => break,
+            }
+        }
+
+        Ok(files)
+    }
+
+    async fn store_files(&mut self, dataset_id: uuid::Uuid, files: &[DataFile]) -> Result<()> {
+        let urls: Vec<&str> = files.iter().map(|file| file.url.as_str()).collect();
+        let formats: Vec<&str> = files.iter().map(|file| file.format.as_str()).collect();
+        let sizes: Vec<Option<i64>> = files.iter().map(|file| file.size_bytes).collect();
+        let checksums: Vec<Option<&str>> =
+            files.iter().map(|file| file.sha256.as_deref()).collect();
+
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO dataset_files (dataset_id, url, format, size_bytes, sha256, source)
+            SELECT $1, batch.url, batch.format, batch.size_bytes, batch.sha256, 'huggingface'
+            FROM UNNEST($2::text[], $3::text[], $4::bigint[], $5::text[])
+                AS batch(url, format, size_bytes, sha256)
+            ON CONFLICT (dataset_id, url) DO UPDATE SET
+                format = EXCLUDED.format,
+                size_bytes = EXCLUDED.size_bytes,
+                sha256 = COALESCE(EXCLUDED.sha256, dataset_files.sha256)
+            "#,
+        )
+        .bind(dataset_id)
+        .bind(&urls)
+        .bind(&formats)
+        .bind(&sizes)
+        .bind(&checksums)
+        .execute(&mut *tx)
+        .await?;
+
+        let removed = sqlx::query(
+            r#"
+            DELETE FROM dataset_files
+            WHERE dataset_id = $1 AND source = 'huggingface' AND url <> ALL($2)
+            "#,
+        )
+        .bind(dataset_id)
+        .bind(&urls)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        self.stats.files_recorded += files.len();
+        self.stats.files_removed += removed.rows_affected();
+        Ok(())
+    }
+
+    async fn process_dataset(&mut self, dataset_id: uuid::Uuid, repo_id: &str) -> Result<()> {
+        let files = self.list_files(repo_id).await?;
+        debug!("{} data files in {}", files.len(), repo_id);
+
+        if files.len() > self.args.max_files {
+            warn!(
+                "Skipping {}: {} data files exceeds --max-files",
+                repo_id,
+                files.len()
+            );
+            self.stats.datasets_skipped += 1;
+            return Ok(());
+        }
+
+        if self.args.dry_run {
+            for file in &files {
+                info!(
+                    "[DRY RUN] {} ({}, {:?} bytes, sha256 {:?})",
+                    file.url, file.format, file.size_bytes, file.sha256
+                );
+            }
+            return Ok(());
+        }
+
+        self.store_files(dataset_id, &files).await
+    }
+
+    async fn run(&mut self) -> Result<()> {
+        let started_at = chrono::Utc::now();
+        let datasets: Vec<(uuid::Uuid, String)> = sqlx::query_as(
+            r#"
+            SELECT id, homepage_url
+            FROM datasets
+            WHERE homepage_url ILIKE '%huggingface.co/datasets/%'
+              AND deleted_at IS NULL
+            ORDER BY name
+            LIMIT $1
+            "#,
+        )
+        .bind((self.args.max_datasets > 0).then_some(self.args.max_datasets))
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch datasets")?;
+
+        info!("Found {} Hugging Face datasets", datasets.len());
+
+        for (dataset_id, homepage_url) in datasets {
+            let Some(repo_id) = hf_repo_id(&homepage_url) else {
+                debug!("Not a dataset repository URL: {}", homepage_url);
+                self.stats.datasets_skipped += 1;
+                continue;
+            };
+            self.stats.datasets_processed += 1;
+
+            if let Err(e) = self.process_dataset(dataset_id, &repo_id).await {
+                warn!("Failed to process {}: {:#}", repo_id, e);
+                self.stats.errors += 1;
+            }
+        }
+
+        if !self.args.dry_run {
+            let recorded = self.stats.files_recorded as i64;
+            let run = record_ingestion_run(&self.pool, "huggingface", started_at, recorded);
+            if let Err(e) = run.await {
+                warn!("Failed to record ingestion run: {}", e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    let args = Args::parse();
+
+    // Setup logging
+    let log_level = if args.verbose {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(log_level)
+        .with_target(false)
+        .compact()
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    info!("Starting Hugging Face dataset file scrape...");
+    if args.dry_run {
+        warn!("DRY RUN MODE - No database writes will occur");
+    }
+
+    let database_url = env::var("POSTGRES_URI").context("POSTGRES_URI must be set")?;
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+    info!("Connected to database");
+
+    let mut scraper = DatasetFilesScraper::new(pool, args)?;
+    scraper.run().await?;
+
+    info!("=== Hugging Face Dataset Files Statistics ===");
+    info!("Datasets processed: {}", scraper.stats.datasets_processed);
+    info!("Datasets skipped:   {}", scraper.stats.datasets_skipped);
+    info!("Files recorded:     {}", scraper.stats.files_recorded);
+    info!("Files removed:      {}", scraper.stats.files_removed);
+    info!("Errors:             {}", scraper.stats.errors);
+
+    Ok(())
+}
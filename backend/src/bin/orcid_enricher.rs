@@ -0,0 +1,344 @@
+//! ORCID Enricher - Attaches ORCID iDs to paper authors
+//!
+//! For each paper with a DOI or arXiv ID, searches the ORCID public API for
+//! researchers who list that work on their record, then matches them to the
+//! paper's author names and stores the matches in `paper_authors`.
+//!
+//! Usage:
+//!     orcid_enricher
+//!     orcid_enricher --max-papers 1000 --recheck-days 90
+
+use anyhow::{Context, Result};
+use backend::normalize_orcid;
+use clap::Parser;
+use dotenvy::dotenv;
+use serde::Deserialize;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::env;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{debug, info, warn, Level};
+use tracing_subscriber::FmtSubscriber;
+
+const ORCID_API: &str = "https://pub.orcid.org/v3.0";
+const USER_AGENT: &str = "CodeWithPapers/1.0 (https://github.com/GeorgePearse/codewithpapers)";
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Enrich paper authors with ORCID iDs", long_about = None)]
+struct Args {
+    /// Maximum number of papers to process (0 = all)
+    #[arg(short, long, default_value_t = 0)]
+    max_papers: i64,
+
+    /// Delay between ORCID API requests in milliseconds
+    #[arg(short, long, default_value_t = 200)]
+    delay_ms: u64,
+
+    /// Re-check papers last searched more than this many days ago
+    #[arg(long, default_value_t = 180)]
+    recheck_days: i32,
+
+    /// Dry run - don't write to database
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Verbose output
+    #[arg(short, long, default_value_t = false)]
+    verbose: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    result: Option<Vec<SearchResult>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResult {
+    #[serde(rename = "orcid-identifier")]
+    orcid_identifier: OrcidIdentifier,
+}
+
+#[derive(Debug, Deserialize)]
+struct OrcidIdentifier {
+    path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Person {
+    name: Option<PersonName>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PersonName {
+    #[serde(rename = "given-names")]
+    given_names: Option<NameValue>,
+    #[serde(rename = "family-name")]
+    family_name: Option<NameValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NameValue {
+    value: String,
+}
+
+#[derive(Debug, Default)]
+struct Stats {
+    papers_processed: usize,
+    authors_matched: usize,
+    errors: usize,
+}
+
+/// Lowercase a name and keep only letters, so "Kaiming He" matches "kaiming he".
+fn name_tokens(name: &str) -> Vec<String> {
+    name.split(|c: char| c.is_whitespace() || c == '-' || c == '.')
+        .map(|token| {
+            token
+                .chars()
+                .filter(|c| c.is_alphabetic())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+/// Whether a paper author name refers to the ORCID record holder: the family
+/// name must match the last name token and the given name's first initial
+/// must match the first token's.
+fn author_matches(author: &str, given_names: &str, family_name: &str) -> bool {
+    let author = name_tokens(author);
+    let family = name_tokens(family_name);
+    let given = name_tokens(given_names);
+
+    let (Some(author_first), Some(author_last)) = (author.first(), author.last()) else {
+        return false;
+    };
+    let (Some(family_last), Some(given_first)) = (family.last(), given.first()) else {
+        return false;
+    };
+
+    author.len() > 1
+        && author_last == family_last
+        && author_first.chars().next() == given_first.chars().next()
+}
+
+struct OrcidEnricher {
+    client: reqwest::Client,
+    pool: PgPool,
+    args: Args,
+    stats: Stats,
+}
+
+impl OrcidEnricher {
+    fn new(pool: PgPool, args: Args) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build()?;
+
+        Ok(Self {
+            client,
+            pool,
+            args,
+            stats: Stats::default(),
+        })
+    }
+
+    async fn get_json<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T> {
+        let value = self
+            .client
+            .get(url)
+            .header("Accept", "application/json")
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .with_context(|| format!("Unexpected response from {}", url))?;
+
+        sleep(Duration::from_millis(self.args.delay_ms)).await;
+        Ok(value)
+    }
+
+    /// ORCID iDs of researchers whose record lists the given work.
+    async fn search_work(&self, doi: Option<&str>, arxiv_id: Option<&str>) -> Result<Vec<String>> {
+        let query = match (doi, arxiv_id) {
+            (Some(doi), _) => format!("doi-self:\"{}\"", doi),
+            (None, Some(arxiv_id)) => format!("arxiv-self:\"{}\"", arxiv_id),
+            (None, None) => return Ok(vec![]),
+        };
+
+        let url = reqwest::Url::parse_with_params(
+            &format!("{}/search/", ORCID_API),
+            &[("q", query.as_str()), ("rows", "50")],
+        )?;
+        let response: SearchResponse = self.get_json(url.as_str()).await?;
+
+        Ok(response
+            .result
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|r| normalize_orcid(&r.orcid_identifier.path))
+            .collect())
+    }
+
+    /// The record holder's (given names, family name), if public.
+    async fn fetch_name(&self, orcid: &str) -> Result<Option<(String, String)>> {
+        let person: Person = self
+            .get_json(&format!("{}/{}/person", ORCID_API, orcid))
+            .await?;
+
+        Ok(person.name.and_then(|name| {
+            Some((name.given_names?.value, name.family_name?.value))
+        }))
+    }
+
+    async fn enrich_paper(
+        &mut self,
+        paper_id: uuid::Uuid,
+        authors: &[String],
+        doi: Option<&str>,
+        arxiv_id: Option<&str>,
+    ) -> Result<()> {
+        let orcids = self.search_work(doi, arxiv_id).await?;
+        debug!("{} ORCID records list paper {}", orcids.len(), paper_id);
+
+        let mut matches = Vec::new();
+        for orcid in orcids {
+            let Some((given_names, family_name)) = self.fetch_name(&orcid).await? else {
+                continue;
+            };
+
+            // Skip ambiguous matches rather than guess between co-authors
+            let positions: Vec<usize> = authors
+                .iter()
+                .enumerate()
+                .filter(|(_, author)| author_matches(author, &given_names, &family_name))
+                .map(|(position, _)| position)
+                .collect();
+            if let [position] = positions[..] {
+                matches.push((position as i32, authors[position].clone(), orcid));
+            }
+        }
+
+        self.stats.authors_matched += matches.len();
+        if self.args.dry_run {
+            for (_, name, orcid) in &matches {
+                info!("[DRY RUN] {} -> {}", name, orcid);
+            }
+            return Ok(());
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        for (position, name, orcid) in &matches {
+            sqlx::query(
+                r#"
+                INSERT INTO paper_authors (paper_id, position, name, orcid, source)
+                VALUES ($1, $2, $3, $4, 'orcid_api')
+                ON CONFLICT (paper_id, position) DO UPDATE SET
+                    name = EXCLUDED.name,
+                    orcid = EXCLUDED.orcid,
+                    source = EXCLUDED.source
+                "#,
+            )
+            .bind(paper_id)
+            .bind(position)
+            .bind(name)
+            .bind(orcid)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query("UPDATE papers SET orcid_checked_at = NOW() WHERE id = $1")
+            .bind(paper_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn run(&mut self) -> Result<()> {
+        let papers: Vec<(uuid::Uuid, serde_json::Value, Option<String>, Option<String>)> =
+            sqlx::query_as(
+                r#"
+                SELECT id, authors, doi, arxiv_id
+                FROM papers
+                WHERE (doi IS NOT NULL OR arxiv_id IS NOT NULL)
+                  AND jsonb_typeof(authors) = 'array'
+                  AND jsonb_array_length(authors) > 0
+                  AND (orcid_checked_at IS NULL
+                       OR orcid_checked_at < NOW() - make_interval(days => $1))
+                ORDER BY orcid_checked_at NULLS FIRST, published_date DESC NULLS LAST
+                LIMIT $2
+                "#,
+            )
+            .bind(self.args.recheck_days)
+            .bind((self.args.max_papers > 0).then_some(self.args.max_papers))
+            .fetch_all(&self.pool)
+            .await
+            .context("Failed to fetch papers")?;
+
+        info!("Found {} papers to check", papers.len());
+
+        for (paper_id, authors, doi, arxiv_id) in papers {
+            let authors: Vec<String> = serde_json::from_value(authors).unwrap_or_default();
+            self.stats.papers_processed += 1;
+
+            if let Err(e) = self
+                .enrich_paper(paper_id, &authors, doi.as_deref(), arxiv_id.as_deref())
+                .await
+            {
+                warn!("Failed to enrich paper {}: {:#}", paper_id, e);
+                self.stats.errors += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    let args = Args::parse();
+
+    // Setup logging
+    let log_level = if args.verbose {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(log_level)
+        .with_target(false)
+        .compact()
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    info!("Starting ORCID enrichment...");
+    if args.dry_run {
+        warn!("DRY RUN MODE - No database writes will occur");
+    }
+
+    let database_url = env::var("POSTGRES_URI").context("POSTGRES_URI must be set")?;
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+    info!("Connected to database");
+
+    let mut enricher = OrcidEnricher::new(pool, args)?;
+    enricher.run().await?;
+
+    info!("=== ORCID Enrichment Statistics ===");
+    info!("Papers processed: {}", enricher.stats.papers_processed);
+    info!("Authors matched:  {}", enricher.stats.authors_matched);
+    info!("Errors:           {}", enricher.stats.errors);
+
+    Ok(())
+}
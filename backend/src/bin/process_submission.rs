@@ -9,7 +9,9 @@
 //!     process_submission --files submission1.yaml submission2.yaml --audit-log audit.json
 
 use anyhow::{Context, Result};
-use chrono::{NaiveDate, Utc};
+use backend::{normalize_doi, record_ingestion_run};
+use backend::text::clean_abstract;
+use chrono::{DateTime, NaiveDate, Utc};
 use clap::Parser;
 use dotenvy::dotenv;
 use rust_decimal::Decimal;
@@ -62,7 +64,11 @@ struct Args {
 #[serde(deny_unknown_fields)]
 pub struct PaperSubmission {
     pub title: String,
-    pub arxiv_id: String,
+    #[serde(default)]
+    pub arxiv_id: Option<String>,
+    /// DOI for journal-published papers (required if there is no arxiv_id)
+    #[serde(default)]
+    pub doi: Option<String>,
     #[serde(default)]
     pub r#abstract: Option<String>,
     #[serde(default)]
@@ -71,6 +77,9 @@ pub struct PaperSubmission {
     pub pdf_url: Option<String>,
     #[serde(default)]
     pub published_date: Option<NaiveDate>,
+    /// Exact publication time, e.g. `2023-01-02T18:59:59Z`
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub authors: Option<Vec<String>>,
 }
@@ -98,6 +107,19 @@ pub struct BenchmarkResultSubmission {
     pub extra_data: Option<serde_json::Value>,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DatasetFileSubmission {
+    pub dataset_name: String,
+    pub url: String,
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub size_bytes: Option<i64>,
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct FullSubmission {
@@ -106,6 +128,8 @@ pub struct FullSubmission {
     pub implementations: Option<Vec<ImplementationSubmission>>,
     #[serde(default)]
     pub benchmark_results: Option<Vec<BenchmarkResultSubmission>>,
+    #[serde(default)]
+    pub dataset_files: Option<Vec<DatasetFileSubmission>>,
 }
 
 // =============================================================================
@@ -171,29 +195,39 @@ async fn insert_paper(
         .as_ref()
         .map(|a| serde_json::to_value(a).unwrap());
 
-    // Use UPSERT to handle duplicates gracefully
-    let row: (Uuid, bool) = sqlx::query_as(
+    let doi = paper.doi.as_deref().and_then(normalize_doi);
+
+    // Use UPSERT to handle duplicates gracefully, keyed on the arXiv ID when
+    // there is one and on the DOI for journal-only papers
+    let conflict_target = if paper.arxiv_id.is_some() { "arxiv_id" } else { "doi" };
+    let row: (Uuid, bool) = sqlx::query_as(&format!(
         r#"
-        INSERT INTO papers (title, abstract, arxiv_id, arxiv_url, pdf_url, published_date, authors)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
-        ON CONFLICT (arxiv_id) DO UPDATE SET
+        INSERT INTO papers (title, abstract, arxiv_id, arxiv_url, pdf_url, published_date, authors, abstract_plain, doi, published_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT ({}) DO UPDATE SET
             title = EXCLUDED.title,
             abstract = COALESCE(EXCLUDED.abstract, papers.abstract),
+            abstract_plain = COALESCE(EXCLUDED.abstract_plain, papers.abstract_plain),
             arxiv_url = COALESCE(EXCLUDED.arxiv_url, papers.arxiv_url),
             pdf_url = COALESCE(EXCLUDED.pdf_url, papers.pdf_url),
+            doi = COALESCE(EXCLUDED.doi, papers.doi),
             published_date = COALESCE(EXCLUDED.published_date, papers.published_date),
-            authors = COALESCE(EXCLUDED.authors, papers.authors),
-            updated_at = NOW()
+            published_at = COALESCE(EXCLUDED.published_at, papers.published_at),
+            authors = COALESCE(EXCLUDED.authors, papers.authors)
         RETURNING id, (xmax = 0)
         "#,
-    )
+        conflict_target
+    ))
     .bind(&paper.title)
     .bind(&paper.r#abstract)
     .bind(&paper.arxiv_id)
     .bind(&paper.arxiv_url)
     .bind(&paper.pdf_url)
-    .bind(paper.published_date)
+    .bind(paper.published_date.or(paper.published_at.map(|at| at.date_naive())))
     .bind(&authors_json)
+    .bind(paper.r#abstract.as_deref().map(clean_abstract))
+    .bind(&doi)
+    .bind(paper.published_at)
     .fetch_one(&mut **tx)
     .await
     .context("Failed to insert paper")?;
@@ -213,8 +247,7 @@ async fn insert_implementation(
         ON CONFLICT (paper_id, github_url) DO UPDATE SET
             framework = COALESCE(EXCLUDED.framework, implementations.framework),
             is_official = EXCLUDED.is_official,
-            stars = COALESCE(EXCLUDED.stars, implementations.stars),
-            updated_at = NOW()
+            stars = COALESCE(EXCLUDED.stars, implementations.stars)
         RETURNING id, (xmax = 0)
         "#,
     )
@@ -296,6 +329,47 @@ async fn insert_benchmark_result(
     Ok(row)
 }
 
+async fn insert_dataset_file(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    file: &DatasetFileSubmission,
+) -> Result<(Uuid, bool)> {
+    let (dataset_id,): (Uuid,) = sqlx::query_as(
+        r#"
+        INSERT INTO datasets (name)
+        VALUES ($1)
+        ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+        RETURNING id
+        "#,
+    )
+    .bind(&file.dataset_name)
+    .fetch_one(&mut **tx)
+    .await
+    .context("Failed to get/create dataset")?;
+
+    let row: (Uuid, bool) = sqlx::query_as(
+        r#"
+        INSERT INTO dataset_files (dataset_id, url, format, size_bytes, sha256, source)
+        VALUES ($1, $2, $3, $4, $5, 'submission')
+        ON CONFLICT (dataset_id, url) DO UPDATE SET
+            format = COALESCE(EXCLUDED.format, dataset_files.format),
+            size_bytes = COALESCE(EXCLUDED.size_bytes, dataset_files.size_bytes),
+            sha256 = COALESCE(EXCLUDED.sha256, dataset_files.sha256),
+            source = EXCLUDED.source
+        RETURNING id, (xmax = 0)
+        "#,
+    )
+    .bind(dataset_id)
+    .bind(&file.url)
+    .bind(file.format.as_deref().map(str::to_lowercase))
+    .bind(file.size_bytes)
+    .bind(file.sha256.as_deref().map(str::to_lowercase))
+    .fetch_one(&mut **tx)
+    .await
+    .context("Failed to insert dataset file")?;
+
+    Ok(row)
+}
+
 async fn process_submission(
     pool: &PgPool,
     submission: &FullSubmission,
@@ -316,12 +390,18 @@ async fn process_submission(
     };
 
     // Insert paper
+    let paper_identifier = submission
+        .paper
+        .arxiv_id
+        .clone()
+        .or_else(|| submission.paper.doi.clone())
+        .unwrap_or_default();
     let paper_result = insert_paper(&mut tx, &submission.paper).await;
     let paper_id = match paper_result {
         Ok((id, inserted)) => {
             audit.records.push(InsertionRecord {
                 table: "papers".to_string(),
-                identifier: submission.paper.arxiv_id.clone(),
+                identifier: paper_identifier.clone(),
                 status: if inserted {
                     InsertionStatus::Success
                 } else {
@@ -339,7 +419,7 @@ async fn process_submission(
         Err(e) => {
             audit.records.push(InsertionRecord {
                 table: "papers".to_string(),
-                identifier: submission.paper.arxiv_id.clone(),
+                identifier: paper_identifier.clone(),
                 status: InsertionStatus::Failed,
                 message: e.to_string(),
                 db_id: None,
@@ -434,6 +514,46 @@ async fn process_submission(
         }
     }
 
+    // Insert dataset files
+    if let Some(ref files) = submission.dataset_files {
+        for file in files {
+            let identifier = format!("{}/{}", file.dataset_name, file.url);
+            match insert_dataset_file(&mut tx, file).await {
+                Ok((id, inserted)) => {
+                    audit.records.push(InsertionRecord {
+                        table: "dataset_files".to_string(),
+                        identifier,
+                        status: if inserted {
+                            InsertionStatus::Success
+                        } else {
+                            InsertionStatus::Duplicate
+                        },
+                        message: if inserted {
+                            "Inserted".to_string()
+                        } else {
+                            "Updated existing".to_string()
+                        },
+                        db_id: Some(id.to_string()),
+                    });
+                }
+                Err(e) => {
+                    audit.records.push(InsertionRecord {
+                        table: "dataset_files".to_string(),
+                        identifier,
+                        status: InsertionStatus::Failed,
+                        message: e.to_string(),
+                        db_id: None,
+                    });
+                    audit.overall_status = InsertionStatus::RolledBack;
+                    audit.error_message = format!("Dataset file insertion failed: {}", e);
+                    audit.rollback_performed = true;
+                    let _ = tx.rollback().await;
+                    return audit;
+                }
+            }
+        }
+    }
+
     // Commit transaction
     match tx.commit().await {
         Ok(_) => {
@@ -558,6 +678,8 @@ async fn main() -> Result<()> {
             audit_entries.push(audit);
         }
     } else {
+        let started_at = Utc::now();
+
         // Connect to database
         let database_url = env::var("POSTGRES_URI")
             .or_else(|_| env::var("DATABASE_URL"))
@@ -593,6 +715,16 @@ async fn main() -> Result<()> {
             let audit = process_submission(&pool, &submission, &path_str, &commit_sha).await;
             audit_entries.push(audit);
         }
+
+        let inserted = audit_entries
+            .iter()
+            .filter(|a| matches!(a.overall_status, InsertionStatus::Success))
+            .count();
+        if let Err(e) =
+            record_ingestion_run(&pool, "submissions", started_at, inserted as i64).await
+        {
+            error!("Failed to record ingestion run: {}", e);
+        }
     }
 
     // Write audit log
@@ -0,0 +1,64 @@
+//! Seed - Load sample papers into a local SQLite database
+//!
+//! Creates the SQLite database named by `DATABASE_URL` if needed, applies
+//! `migrations_sqlite/`, and upserts the sample catalog in `seed/sample.json`
+//! (papers with implementations, datasets, benchmarks and results), so
+//! `cargo run` has something to serve without Postgres. Safe to re-run.
+//!
+//! Usage:
+//!     DATABASE_URL=sqlite://data/cwp.db seed
+
+use anyhow::{bail, Context, Result};
+use backend::sqlite;
+use clap::Parser;
+use dotenvy::dotenv;
+use std::env;
+use tracing::{info, Level};
+use tracing_subscriber::FmtSubscriber;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Load sample papers into SQLite", long_about = None)]
+struct Args {
+    /// Verbose output
+    #[arg(short, long, default_value_t = false)]
+    verbose: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    let args = Args::parse();
+
+    // Setup logging
+    let log_level = if args.verbose {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(log_level)
+        .with_target(false)
+        .compact()
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    let database_url = env::var("DATABASE_URL").context("DATABASE_URL must be set")?;
+    if !sqlite::is_sqlite_url(&database_url) {
+        bail!(
+            "DATABASE_URL must be an sqlite:// URL; to load sample data into Postgres, \
+             run scripts/test_load_sample.py"
+        );
+    }
+
+    let pool = sqlite::connect(&database_url).await?;
+    info!("Opened {}", database_url);
+
+    let stats = sqlite::seed(&pool).await?;
+    info!(
+        "Seeded {} papers, {} implementations, {} datasets, {} benchmarks and {} results",
+        stats.papers, stats.implementations, stats.datasets, stats.benchmarks, stats.results
+    );
+
+    Ok(())
+}
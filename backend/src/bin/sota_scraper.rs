@@ -4,6 +4,7 @@
 //! and populates the database with tasks, datasets, and benchmarks.
 
 use anyhow::{Context, Result};
+use backend::record_ingestion_run;
 use clap::Parser;
 use dotenvy::dotenv;
 use scraper::{Html, Selector};
@@ -50,7 +51,7 @@ struct Task {
     url: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct ScraperStats {
     tasks_found: usize,
     tasks_processed: usize,
@@ -59,18 +60,6 @@ struct ScraperStats {
     errors: usize,
 }
 
-impl Default for ScraperStats {
-    fn default() -> Self {
-        Self {
-            tasks_found: 0,
-            tasks_processed: 0,
-            datasets_inserted: 0,
-            benchmarks_inserted: 0,
-            errors: 0,
-        }
-    }
-}
-
 struct Scraper {
     client: reqwest::Client,
     pool: Option<PgPool>,
@@ -303,6 +292,7 @@ async fn main() -> Result<()> {
         Some(pool)
     };
 
+    let started_at = chrono::Utc::now();
     let mut scraper = Scraper::new(pool, args.delay_ms, args.dry_run).await?;
 
     // Scrape SOTA page
@@ -332,6 +322,13 @@ async fn main() -> Result<()> {
     }
 
     scraper.print_stats();
+
+    if let Some(pool) = &scraper.pool {
+        let items = scraper.stats.datasets_inserted + scraper.stats.benchmarks_inserted;
+        if let Err(e) = record_ingestion_run(pool, "sota", started_at, items as i64).await {
+            warn!("Failed to record ingestion run: {}", e);
+        }
+    }
     info!("Scraping complete.");
 
     Ok(())
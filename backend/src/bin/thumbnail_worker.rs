@@ -0,0 +1,195 @@
+//! Thumbnail Worker - Renders a preview image for each paper
+//!
+//! Downloads each paper's PDF into the paper store (if it isn't cached yet)
+//! and renders the first page to a PNG thumbnail, which the API serves at
+//! `/api/papers/{id}/thumbnail` for list views.
+//!
+//! Rendering shells out to `pdftoppm` from poppler-utils, which must be on PATH.
+//!
+//! Usage:
+//!     thumbnail_worker
+//!     thumbnail_worker --store-path ./data/paper_store --width 400 --max-papers 100
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use dotenvy::dotenv;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::sleep;
+use tracing::{debug, info, warn, Level};
+use tracing_subscriber::FmtSubscriber;
+
+use backend::storage::{pdf_source_url, PaperStore};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Render first-page thumbnails for paper PDFs", long_about = None)]
+struct Args {
+    /// Path of the paper store shared with the API server
+    #[arg(long, default_value = "./data/paper_store")]
+    store_path: PathBuf,
+
+    /// Thumbnail width in pixels (height keeps the page's aspect ratio)
+    #[arg(long, default_value_t = 400)]
+    width: u32,
+
+    /// Maximum number of papers to process (0 = all)
+    #[arg(short, long, default_value_t = 0)]
+    max_papers: usize,
+
+    /// Delay after each PDF download in milliseconds (arXiv asks for 3s)
+    #[arg(short, long, default_value_t = 3000)]
+    delay_ms: u64,
+
+    /// Re-render thumbnails that already exist
+    #[arg(long, default_value_t = false)]
+    force: bool,
+
+    /// Verbose output
+    #[arg(short, long, default_value_t = false)]
+    verbose: bool,
+}
+
+#[derive(Debug, Default)]
+struct Stats {
+    papers_processed: usize,
+    thumbnails_rendered: usize,
+    thumbnails_skipped: usize,
+    errors: usize,
+}
+
+/// Render the first page of a PDF to a PNG `width` pixels wide.
+async fn render_first_page(pdf_path: &Path, thumbnail_path: &Path, width: u32) -> Result<()> {
+    // pdftoppm appends ".png" to the output prefix it is given
+    let prefix = thumbnail_path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+    let mut rendered_path = prefix.clone().into_os_string();
+    rendered_path.push(".png");
+
+    let output = Command::new("pdftoppm")
+        .args(["-png", "-f", "1", "-l", "1", "-singlefile"])
+        .args(["-scale-to-x", &width.to_string(), "-scale-to-y", "-1"])
+        .arg(pdf_path)
+        .arg(&prefix)
+        .output()
+        .await
+        .context("Failed to run pdftoppm (is poppler-utils installed?)")?;
+
+    if !output.status.success() {
+        bail!(
+            "pdftoppm failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    tokio::fs::rename(&rendered_path, thumbnail_path)
+        .await
+        .with_context(|| format!("Failed to move thumbnail into place at {:?}", thumbnail_path))?;
+
+    Ok(())
+}
+
+async fn run(pool: &PgPool, store: &PaperStore, args: &Args) -> Result<Stats> {
+    let papers: Vec<(uuid::Uuid, Option<String>, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT id, pdf_url, arxiv_id
+        FROM papers
+        WHERE (pdf_url IS NOT NULL OR arxiv_id IS NOT NULL)
+          AND deleted_at IS NULL
+        ORDER BY published_date DESC NULLS LAST, id
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch papers")?;
+
+    info!("Found {} papers with a PDF source", papers.len());
+
+    let mut stats = Stats::default();
+
+    for (paper_id, pdf_url, arxiv_id) in papers {
+        if args.max_papers > 0 && stats.papers_processed >= args.max_papers {
+            info!("Reached max papers limit ({})", args.max_papers);
+            break;
+        }
+
+        let thumbnail_path = store.thumbnail_path(paper_id);
+        if !args.force && thumbnail_path.is_file() {
+            stats.thumbnails_skipped += 1;
+            continue;
+        }
+
+        stats.papers_processed += 1;
+
+        let Some(url) = pdf_source_url(pdf_url, arxiv_id.as_deref()) else {
+            continue;
+        };
+
+        let needs_download = !store.pdf_path(paper_id).is_file();
+        let result = async {
+            let pdf_path = store.fetch_pdf(paper_id, &url).await?;
+            render_first_page(&pdf_path, &thumbnail_path, args.width).await
+        }
+        .await;
+
+        match result {
+            Ok(()) => {
+                debug!("Rendered thumbnail for {}", paper_id);
+                stats.thumbnails_rendered += 1;
+            }
+            Err(e) => {
+                warn!("Failed to render thumbnail for {}: {:#}", paper_id, e);
+                stats.errors += 1;
+            }
+        }
+
+        if needs_download {
+            sleep(Duration::from_millis(args.delay_ms)).await;
+        }
+    }
+
+    Ok(stats)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    let args = Args::parse();
+
+    // Setup logging
+    let log_level = if args.verbose {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(log_level)
+        .with_target(false)
+        .compact()
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    info!("Starting thumbnail worker...");
+
+    let database_url = env::var("POSTGRES_URI").context("POSTGRES_URI must be set")?;
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+    info!("Connected to database");
+
+    let store = PaperStore::open(&args.store_path).context("Failed to open paper store")?;
+
+    let stats = run(&pool, &store, &args).await?;
+
+    info!("=== Thumbnail Worker Statistics ===");
+    info!("Papers processed:     {}", stats.papers_processed);
+    info!("Thumbnails rendered:  {}", stats.thumbnails_rendered);
+    info!("Already rendered:     {}", stats.thumbnails_skipped);
+    info!("Errors:               {}", stats.errors);
+
+    Ok(())
+}
@@ -0,0 +1,327 @@
+//! Translate Abstracts - Machine-translate paper titles and abstracts
+//!
+//! Fills the `paper_translations` table for one or more target languages,
+//! which the API serves through `?lang=` on the paper endpoints. Translation
+//! backends are pluggable: implement `Translator` and add it to `Backend`.
+//!
+//! Supported backends:
+//!   - libretranslate: self-hostable; LIBRETRANSLATE_URL, LIBRETRANSLATE_API_KEY
+//!   - deepl: DEEPL_API_KEY (free-tier keys ending in ":fx" are detected)
+//!
+//! Usage:
+//!     translate_abstracts --lang de,fr
+//!     translate_abstracts --lang zh --backend deepl --max-papers 500
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, ValueEnum};
+use dotenvy::dotenv;
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::env;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{debug, info, warn, Level};
+use tracing_subscriber::FmtSubscriber;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Backend {
+    Libretranslate,
+    Deepl,
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Translate paper titles and abstracts", long_about = None)]
+struct Args {
+    /// Target language codes, comma separated (e.g. de,fr,pt-br)
+    #[arg(short, long, value_delimiter = ',', required = true)]
+    lang: Vec<String>,
+
+    /// Translation backend
+    #[arg(short, long, value_enum, default_value_t = Backend::Libretranslate)]
+    backend: Backend,
+
+    /// Maximum number of papers to translate per language (0 = all)
+    #[arg(short, long, default_value_t = 0)]
+    max_papers: i64,
+
+    /// Delay between requests in milliseconds
+    #[arg(short, long, default_value_t = 500)]
+    delay_ms: u64,
+
+    /// Dry run - translate but don't write to database
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Verbose output
+    #[arg(short, long, default_value_t = false)]
+    verbose: bool,
+}
+
+/// A machine translation service that can translate English text.
+trait Translator {
+    /// Name recorded in `paper_translations.provider`
+    fn name(&self) -> &'static str;
+
+    /// Translate each text into `target` (a lowercase language code),
+    /// returning the translations in the same order.
+    async fn translate(&self, texts: &[&str], target: &str) -> Result<Vec<String>>;
+}
+
+/// LibreTranslate (https://libretranslate.com), public or self-hosted.
+struct LibreTranslate {
+    client: reqwest::Client,
+    url: String,
+    api_key: Option<String>,
+}
+
+impl LibreTranslate {
+    fn from_env(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            url: env::var("LIBRETRANSLATE_URL")
+                .unwrap_or_else(|_| "https://libretranslate.com".to_string()),
+            api_key: env::var("LIBRETRANSLATE_API_KEY").ok(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct LibreTranslateResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: Vec<String>,
+}
+
+impl Translator for LibreTranslate {
+    fn name(&self) -> &'static str {
+        "libretranslate"
+    }
+
+    async fn translate(&self, texts: &[&str], target: &str) -> Result<Vec<String>> {
+        let response: LibreTranslateResponse = self
+            .client
+            .post(format!("{}/translate", self.url.trim_end_matches('/')))
+            .json(&json!({
+                "q": texts,
+                "source": "en",
+                "target": target,
+                "format": "text",
+                "api_key": self.api_key,
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Unexpected LibreTranslate response")?;
+
+        Ok(response.translated_text)
+    }
+}
+
+/// DeepL API (https://www.deepl.com/docs-api).
+struct DeepL {
+    client: reqwest::Client,
+    api_key: String,
+}
+
+impl DeepL {
+    fn from_env(client: reqwest::Client) -> Result<Self> {
+        let api_key = env::var("DEEPL_API_KEY").context("DEEPL_API_KEY must be set")?;
+        Ok(Self { client, api_key })
+    }
+}
+
+#[derive(Deserialize)]
+struct DeepLResponse {
+    translations: Vec<DeepLTranslation>,
+}
+
+#[derive(Deserialize)]
+struct DeepLTranslation {
+    text: String,
+}
+
+impl Translator for DeepL {
+    fn name(&self) -> &'static str {
+        "deepl"
+    }
+
+    async fn translate(&self, texts: &[&str], target: &str) -> Result<Vec<String>> {
+        // Free-tier keys are only valid against the free API host
+        let host = if self.api_key.ends_with(":fx") {
+            "https://api-free.deepl.com"
+        } else {
+            "https://api.deepl.com"
+        };
+
+        let response: DeepLResponse = self
+            .client
+            .post(format!("{}/v2/translate", host))
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .json(&json!({
+                "text": texts,
+                "source_lang": "EN",
+                "target_lang": target.to_uppercase(),
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Unexpected DeepL response")?;
+
+        Ok(response.translations.into_iter().map(|t| t.text).collect())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Stats {
+    papers_translated: usize,
+    errors: usize,
+}
+
+async fn translate_language<T: Translator>(
+    pool: &PgPool,
+    translator: &T,
+    language: &str,
+    args: &Args,
+    stats: &mut Stats,
+) -> Result<()> {
+    let papers: Vec<(uuid::Uuid, String, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT p.id, p.title, COALESCE(p.abstract_plain, p.abstract)
+        FROM papers p
+        WHERE NOT EXISTS (
+            SELECT 1 FROM paper_translations t
+            WHERE t.paper_id = p.id AND t.language = $1
+        )
+        ORDER BY p.published_date DESC NULLS LAST, p.id
+        LIMIT $2
+        "#,
+    )
+    .bind(language)
+    .bind((args.max_papers > 0).then_some(args.max_papers))
+    .fetch_all(pool)
+    .await
+    .context("Failed to fetch papers")?;
+
+    info!("Translating {} papers into '{}'", papers.len(), language);
+
+    for (paper_id, title, abstract_text) in papers {
+        let mut texts = vec![title.as_str()];
+        texts.extend(abstract_text.as_deref());
+
+        let translated = match translator.translate(&texts, language).await {
+            Ok(translated) if translated.len() == texts.len() => translated,
+            Ok(translated) => {
+                warn!(
+                    "Expected {} translations for {}, got {}",
+                    texts.len(),
+                    paper_id,
+                    translated.len()
+                );
+                stats.errors += 1;
+                continue;
+            }
+            Err(e) => {
+                warn!("Failed to translate {}: {:#}", paper_id, e);
+                stats.errors += 1;
+                continue;
+            }
+        };
+
+        let mut translated = translated.into_iter();
+        let translated_title = translated.next();
+        let translated_abstract = translated.next();
+        debug!("Translated {} into '{}'", paper_id, language);
+
+        if !args.dry_run {
+            sqlx::query(
+                r#"
+                INSERT INTO paper_translations (paper_id, language, title, abstract, provider)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (paper_id, language) DO UPDATE SET
+                    title = EXCLUDED.title,
+                    abstract = EXCLUDED.abstract,
+                    provider = EXCLUDED.provider
+                "#,
+            )
+            .bind(paper_id)
+            .bind(language)
+            .bind(&translated_title)
+            .bind(&translated_abstract)
+            .bind(translator.name())
+            .execute(pool)
+            .await
+            .context("Failed to save translation")?;
+        }
+
+        stats.papers_translated += 1;
+        sleep(Duration::from_millis(args.delay_ms)).await;
+    }
+
+    Ok(())
+}
+
+async fn run<T: Translator>(pool: &PgPool, translator: &T, args: &Args) -> Result<Stats> {
+    let mut stats = Stats::default();
+
+    for language in &args.lang {
+        let language = language.trim().to_lowercase();
+        if language.is_empty() || language == "en" {
+            bail!("Invalid target language '{}'", language);
+        }
+        translate_language(pool, translator, &language, args, &mut stats).await?;
+    }
+
+    Ok(stats)
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv().ok();
+
+    let args = Args::parse();
+
+    // Setup logging
+    let log_level = if args.verbose {
+        Level::DEBUG
+    } else {
+        Level::INFO
+    };
+    let subscriber = FmtSubscriber::builder()
+        .with_max_level(log_level)
+        .with_target(false)
+        .compact()
+        .finish();
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    info!("Starting abstract translation ({:?})...", args.backend);
+    if args.dry_run {
+        warn!("DRY RUN MODE - No database writes will occur");
+    }
+
+    let database_url = env::var("POSTGRES_URI").context("POSTGRES_URI must be set")?;
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&database_url)
+        .await
+        .context("Failed to connect to database")?;
+    info!("Connected to database");
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(60))
+        .build()?;
+
+    let stats = match args.backend {
+        Backend::Libretranslate => run(&pool, &LibreTranslate::from_env(client), &args).await?,
+        Backend::Deepl => run(&pool, &DeepL::from_env(client)?, &args).await?,
+    };
+
+    info!("=== Translation Statistics ===");
+    info!("Papers translated: {}", stats.papers_translated);
+    info!("Errors:            {}", stats.errors);
+
+    Ok(())
+}
@@ -8,7 +8,8 @@
 //!     validate_submission submissions/  # validates all YAML files in directory
 
 use anyhow::Result;
-use chrono::NaiveDate;
+use backend::normalize_doi;
+use chrono::{DateTime, NaiveDate, Utc};
 use clap::Parser;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -56,7 +57,11 @@ enum OutputFormat {
 #[serde(deny_unknown_fields)]
 pub struct PaperSubmission {
     pub title: String,
-    pub arxiv_id: String,
+    #[serde(default)]
+    pub arxiv_id: Option<String>,
+    /// DOI for journal-published papers (required if there is no arxiv_id)
+    #[serde(default)]
+    pub doi: Option<String>,
     #[serde(default)]
     pub r#abstract: Option<String>,
     #[serde(default)]
@@ -65,6 +70,9 @@ pub struct PaperSubmission {
     pub pdf_url: Option<String>,
     #[serde(default)]
     pub published_date: Option<NaiveDate>,
+    /// Exact publication time, e.g. `2023-01-02T18:59:59Z`
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub authors: Option<Vec<String>>,
 }
@@ -94,6 +102,20 @@ pub struct BenchmarkResultSubmission {
     pub extra_data: Option<serde_json::Value>,
 }
 
+/// Dataset download file submission data from YAML
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DatasetFileSubmission {
+    pub dataset_name: String,
+    pub url: String,
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub size_bytes: Option<i64>,
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
 /// Full submission containing a paper and optionally related data
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -103,6 +125,8 @@ pub struct FullSubmission {
     pub implementations: Option<Vec<ImplementationSubmission>>,
     #[serde(default)]
     pub benchmark_results: Option<Vec<BenchmarkResultSubmission>>,
+    #[serde(default)]
+    pub dataset_files: Option<Vec<DatasetFileSubmission>>,
 }
 
 // =============================================================================
@@ -254,9 +278,29 @@ fn validate_file(path: &PathBuf) -> ValidationResult {
         );
     }
 
-    // arXiv ID validation
-    if let Err(e) = validate_arxiv_id(&paper.arxiv_id) {
-        result.add_error("paper.arxiv_id", &e, None);
+    // Identifier validation: papers need an arXiv ID, a DOI, or both
+    if paper.arxiv_id.is_none() && paper.doi.is_none() {
+        result.add_error(
+            "paper.arxiv_id",
+            "Either arxiv_id or doi is required",
+            Some("Journal papers without an arXiv preprint can use their DOI"),
+        );
+    }
+
+    if let Some(ref arxiv_id) = paper.arxiv_id {
+        if let Err(e) = validate_arxiv_id(arxiv_id) {
+            result.add_error("paper.arxiv_id", &e, None);
+        }
+    }
+
+    if let Some(ref doi) = paper.doi {
+        if normalize_doi(doi).is_none() {
+            result.add_error(
+                "paper.doi",
+                &format!("Invalid DOI format: '{}'. Expected format like '10.1038/nature14539'", doi),
+                None,
+            );
+        }
     }
 
     // URL validations (if provided)
@@ -290,6 +334,23 @@ fn validate_file(path: &PathBuf) -> ValidationResult {
         }
     }
 
+    if let Some(published_at) = paper.published_at {
+        if published_at > Utc::now() {
+            result.add_error(
+                "paper.published_at",
+                "Publication time cannot be in the future",
+                None,
+            );
+        }
+        if paper.published_date.is_some_and(|date| date != published_at.date_naive()) {
+            result.add_error(
+                "paper.published_at",
+                "Publication time does not fall on published_date",
+                None,
+            );
+        }
+    }
+
     // Validate implementations
     if let Some(ref impls) = submission.implementations {
         for (i, impl_) in impls.iter().enumerate() {
@@ -354,6 +415,52 @@ fn validate_file(path: &PathBuf) -> ValidationResult {
         }
     }
 
+    // Validate dataset files
+    if let Some(ref files) = submission.dataset_files {
+        for (i, file) in files.iter().enumerate() {
+            let field_prefix = format!("dataset_files[{}]", i);
+
+            if file.dataset_name.trim().is_empty() {
+                result.add_error(
+                    &format!("{}.dataset_name", field_prefix),
+                    "Dataset name cannot be empty",
+                    None,
+                );
+            }
+
+            if let Err(e) = validate_url(&file.url, "url") {
+                result.add_error(&format!("{}.url", field_prefix), &e, None);
+            }
+
+            if file.size_bytes.is_some_and(|size| size < 0) {
+                result.add_error(
+                    &format!("{}.size_bytes", field_prefix),
+                    "File size cannot be negative",
+                    None,
+                );
+            }
+
+            match &file.sha256 {
+                Some(sha256) => {
+                    if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                        result.add_error(
+                            &format!("{}.sha256", field_prefix),
+                            "SHA-256 must be 64 hexadecimal characters",
+                            Some("Use the output of `sha256sum <file>`"),
+                        );
+                    }
+                }
+                None => {
+                    result.add_warning(
+                        &format!("{}.sha256", field_prefix),
+                        "No SHA-256 checksum",
+                        Some("Add a checksum so downloads can be verified"),
+                    );
+                }
+            }
+        }
+    }
+
     // Add warnings for missing optional but recommended fields
     if paper.r#abstract.is_none() {
         result.add_warning(
@@ -371,7 +478,7 @@ fn validate_file(path: &PathBuf) -> ValidationResult {
         );
     }
 
-    if paper.published_date.is_none() {
+    if paper.published_date.is_none() && paper.published_at.is_none() {
         result.add_warning(
             "paper.published_date",
             "No publication date",
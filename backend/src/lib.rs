@@ -1,15 +1,37 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    routing::get,
+    async_trait,
+    body::Bytes,
+    extract::{FromRequestParts, Path, Query, Request, State},
+    http::{header, request::Parts, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Redirect, Response,
+    },
+    routing::{get, post, put},
     Json, Router,
 };
+use futures::{Stream, StreamExt};
+use repository::{
+    DependencyQuery, GithubRepoUpdate, ImplementationQuery, PaperQuery, Repository,
+    TaskParentChange,
+};
 use serde::{Deserialize, Serialize};
 use sqlx::{Pool, Postgres};
-use std::sync::Arc;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 use tower_http::cors::{Any, CorsLayer};
+use tower_http::services::ServeFile;
 
+pub mod arxiv;
+pub mod postgres;
+pub mod repository;
 pub mod search;
+pub mod sqlite;
+pub mod storage;
+pub mod text;
 
 // ============================================================================
 // Response Types
@@ -34,13 +56,57 @@ pub struct Paper {
     pub id: uuid::Uuid,
     pub title: String,
     pub r#abstract: Option<String>,
+    /// Abstract with LaTeX markup and HTML entities cleaned up
+    pub abstract_plain: Option<String>,
     pub arxiv_id: Option<String>,
     pub arxiv_url: Option<String>,
     pub pdf_url: Option<String>,
+    /// Normalized DOI, e.g. `10.1038/nature14539`
+    pub doi: Option<String>,
     pub published_date: Option<chrono::NaiveDate>,
+    /// Exact publication time (arXiv v1 submission), when known
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
     pub authors: Option<serde_json::Value>,
+    /// Whether the paper has any live implementation (maintained by a trigger)
+    pub has_code: bool,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Set when soft-deleted; such rows are only returned with `include_deleted`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Translated title/abstract for the requested `?lang=`, when available
+    #[sqlx(skip)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<PaperTranslation>,
+    /// Every loaded arXiv version of this paper, latest first
+    #[sqlx(skip)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<ArxivVersion>,
+}
+
+/// One arXiv version of a paper, stored as its own `papers` row.
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug, Clone)]
+pub struct ArxivVersion {
+    #[serde(skip)]
+    pub arxiv_base_id: String,
+    pub id: uuid::Uuid,
+    pub arxiv_id: String,
+    /// `N` from a `vN` suffix; `None` for unversioned IDs
+    pub version: Option<i32>,
+    pub published_date: Option<chrono::NaiveDate>,
+}
+
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug, Clone)]
+pub struct PaperTranslation {
+    #[serde(skip)]
+    pub paper_id: uuid::Uuid,
+    /// Lowercase language code, e.g. `de` or `pt-br`
+    pub language: String,
+    pub title: Option<String>,
+    pub r#abstract: Option<String>,
+    /// Translation backend that produced the text
+    pub provider: String,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
@@ -51,6 +117,15 @@ pub struct PaperSummary {
     pub published_date: Option<chrono::NaiveDate>,
 }
 
+/// An author of a paper with a known ORCID iD.
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
+pub struct PaperAuthor {
+    /// Index into the paper's `authors` array
+    pub position: i32,
+    pub name: String,
+    pub orcid: String,
+}
+
 #[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
 pub struct Dataset {
     pub id: uuid::Uuid,
@@ -65,6 +140,25 @@ pub struct Dataset {
     pub paper_url: Option<String>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A downloadable file of a dataset. Mirrors of one file share a `sha256`.
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
+pub struct DatasetFile {
+    pub id: uuid::Uuid,
+    pub dataset_id: uuid::Uuid,
+    pub url: String,
+    /// File format, e.g. `parquet` or `tar.gz`
+    pub format: Option<String>,
+    pub size_bytes: Option<i64>,
+    /// Lowercase hex SHA-256 of the file contents
+    pub sha256: Option<String>,
+    /// Where the entry came from: `submission` or `huggingface`
+    pub source: String,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
@@ -76,6 +170,8 @@ pub struct Benchmark {
     pub description: Option<String>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
@@ -86,10 +182,46 @@ pub struct Implementation {
     pub framework: Option<String>,
     pub stars: Option<i32>,
     pub is_official: Option<bool>,
+    pub has_dockerfile: Option<bool>,
+    pub has_conda_env: Option<bool>,
+    pub has_pinned_requirements: Option<bool>,
+    /// Bytes of code per language from GitHub, e.g. `{"Python": 123456}`
+    pub languages: Option<serde_json::Value>,
+    /// Repository is archived (read-only) on GitHub
+    pub archived: Option<bool>,
+    /// Repository has been disabled by GitHub
+    pub disabled: Option<bool>,
+    /// Last push to the repository, from GitHub
+    pub pushed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
+pub struct Package {
+    pub id: uuid::Uuid,
+    pub implementation_id: uuid::Uuid,
+    /// Package registry: pypi, crates, or npm
+    pub registry: String,
+    pub name: String,
+    pub url: String,
+    /// Shell command that installs the package (e.g. `pip install foo`)
+    pub install_command: Option<String>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
+pub struct Dependency {
+    /// PEP 503 normalized package name
+    pub name: String,
+    pub version_spec: Option<String>,
+    /// Manifest the dependency was read from (requirements.txt, pyproject.toml)
+    pub source: String,
+}
+
 #[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
 pub struct BenchmarkResult {
     pub id: uuid::Uuid,
@@ -100,6 +232,9 @@ pub struct BenchmarkResult {
     pub metric_value: rust_decimal::Decimal,
     pub extra_data: Option<serde_json::Value>,
     pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 // ============================================================================
@@ -127,15 +262,87 @@ impl Default for PaginationParams {
     }
 }
 
+/// Query parameter accepted by every read endpoint; see [`IncludeDeleted`]
+#[derive(Deserialize, Debug, Default)]
+pub struct VisibilityParams {
+    /// Also return soft-deleted rows (requires the admin token)
+    pub include_deleted: Option<bool>,
+}
+
+/// Query parameters for a single paper
+#[derive(Deserialize, Debug, Default)]
+pub struct PaperParams {
+    /// Language code for a translated title/abstract (e.g. `de`)
+    pub lang: Option<String>,
+}
+
+/// Query parameters for listing implementations
+#[derive(Deserialize, Debug, Default)]
+pub struct ImplementationParams {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    /// Filter: repos with a Dockerfile, conda env, or pinned requirements
+    pub reproducible: Option<bool>,
+    /// Filter: repos that are archived on GitHub
+    pub archived: Option<bool>,
+}
+
+/// Query parameters for finding papers by code dependency
+#[derive(Deserialize, Debug, Default)]
+pub struct DependencyParams {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    /// Filter: only consider official implementations
+    pub official: Option<bool>,
+}
+
 // ============================================================================
 // Joined Response Types
 // ============================================================================
 
+/// Share of code in one language, summed across a paper's implementations.
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
+pub struct LanguageBreakdown {
+    pub language: String,
+    pub bytes: i64,
+    /// Percentage of all bytes across the paper's implementations
+    pub percentage: f64,
+}
+
 #[derive(Serialize, Debug)]
 pub struct PaperWithImplementations {
     #[serde(flatten)]
     pub paper: Paper,
     pub implementations: Vec<Implementation>,
+    pub languages: Vec<LanguageBreakdown>,
+    /// Authors whose ORCID iD is known
+    pub author_orcids: Vec<PaperAuthor>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AuthorWithPapers {
+    pub orcid: String,
+    /// Name as it appears on the author's most recent paper
+    pub name: String,
+    pub papers: Vec<PaperSummary>,
+}
+
+/// An implementation with its position in the paper's ranking.
+#[derive(Serialize, sqlx::FromRow, Debug)]
+pub struct RankedImplementation {
+    /// 1-based position under `IMPLEMENTATION_RANKING`
+    pub rank: i64,
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub implementation: Implementation,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ImplementationDetail {
+    #[serde(flatten)]
+    pub implementation: Implementation,
+    pub packages: Vec<Package>,
+    pub dependencies: Vec<Dependency>,
 }
 
 #[derive(Serialize, Debug)]
@@ -145,12 +352,94 @@ pub struct BenchmarkWithDataset {
     pub dataset: Option<Dataset>,
 }
 
+/// A task in the taxonomy with its subtasks. Counts roll up everything
+/// below it, so an area like Computer Vision covers all of its tasks.
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
+pub struct TaskNode {
+    pub id: uuid::Uuid,
+    pub name: String,
+    pub parent_id: Option<uuid::Uuid>,
+    /// Benchmarks of this task and all of its subtasks
+    pub benchmark_count: i64,
+    /// Distinct papers with results on those benchmarks
+    pub paper_count: i64,
+    /// Path from the root down to the parent, when fetched by ID
+    #[sqlx(skip)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ancestors: Vec<TaskSummary>,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub children: Vec<TaskNode>,
+}
+
+#[derive(Serialize, Deserialize, sqlx::FromRow, Debug)]
+pub struct TaskSummary {
+    pub id: uuid::Uuid,
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TaskParentUpdate {
+    /// Name of the parent task, created if it doesn't exist yet;
+    /// `null` moves the task to the top level
+    pub parent: Option<String>,
+}
+
+/// Response body for shields.io's endpoint badge
+/// (https://shields.io/badges/endpoint-badge).
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ShieldsBadge {
+    pub schema_version: u8,
+    pub label: String,
+    pub message: String,
+    pub color: String,
+    pub cache_seconds: u32,
+}
+
+/// Outcome of a GitHub webhook delivery.
+#[derive(Serialize, Debug)]
+pub struct WebhookResponse {
+    pub event: String,
+    /// `owner/repo` the event was about, if any
+    pub repository: Option<String>,
+    pub implementations_updated: u64,
+}
+
+/// A newly ingested paper, as announced on [`PAPER_EVENTS_CHANNEL`] and
+/// streamed to clients of `GET /api/papers/stream`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PaperEvent {
+    pub id: uuid::Uuid,
+    pub title: String,
+    pub arxiv_id: Option<String>,
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// arXiv categories, primary first
+    pub categories: Vec<String>,
+}
+
+/// Full-text search health, from `GET /api/search/status`.
+#[derive(Serialize, Debug)]
+pub struct SearchStatus {
+    /// Whether search uses the Tantivy index (otherwise Postgres ILIKE)
+    pub available: bool,
+    #[serde(flatten)]
+    pub index: Option<search::IndexStatus>,
+}
+
 #[derive(Serialize, Debug)]
 pub struct StatsResponse {
     pub papers_count: i64,
     pub datasets_count: i64,
     pub benchmarks_count: i64,
     pub implementations_count: i64,
+    pub results_count: i64,
+    pub papers_with_code_count: i64,
+    /// Share of papers with code, in percent (one decimal)
+    pub papers_with_code_percent: f64,
+    /// When each ingestion source (`archive`, `github`, `sota`, `arxiv`,
+    /// `submissions`) last finished a run; sources that never ran are absent
+    pub last_ingested_at: BTreeMap<String, chrono::DateTime<chrono::Utc>>,
 }
 
 // ============================================================================
@@ -159,41 +448,372 @@ pub struct StatsResponse {
 
 #[derive(Clone)]
 pub struct AppState {
-    pub pool: Pool<Postgres>,
+    pub repository: Arc<dyn Repository>,
     pub search_index: Option<Arc<search::SearchIndex>>,
+    pub paper_store: Option<Arc<storage::PaperStore>>,
+    pub config: AppConfig,
+}
+
+/// Optional API features, configured from the environment by `main`. Each
+/// feature is disabled while its setting is unset.
+#[derive(Clone, Debug, Default)]
+pub struct AppConfig {
+    /// Bearer token for admin-only options such as `include_deleted`
+    pub admin_token: Option<String>,
+    /// Shared secret that signs GitHub webhook deliveries
+    pub github_webhook_secret: Option<String>,
+}
+
+/// A read-only Postgres replica, health-checked in the background so reads
+/// fall back to the primary while it is down.
+#[derive(Clone, Debug)]
+pub struct ReadReplica {
+    pool: Pool<Postgres>,
+    healthy: Arc<AtomicBool>,
+}
+
+impl ReadReplica {
+    /// Wrap a replica pool and check it every `check_interval` until the
+    /// last clone is dropped. It counts as unhealthy until the first check
+    /// succeeds.
+    pub fn new(pool: Pool<Postgres>, check_interval: Duration) -> Self {
+        let healthy = Arc::new(AtomicBool::new(false));
+
+        let weak_healthy = Arc::downgrade(&healthy);
+        let check_pool = pool.clone();
+        tokio::spawn(async move {
+            loop {
+                let ok = tokio::time::timeout(
+                    Duration::from_secs(2),
+                    sqlx::query("SELECT 1").execute(&check_pool),
+                )
+                .await
+                .is_ok_and(|result| result.is_ok());
+
+                let Some(healthy) = weak_healthy.upgrade() else {
+                    break;
+                };
+                healthy.store(ok, Ordering::Relaxed);
+                drop(healthy);
+
+                tokio::time::sleep(check_interval).await;
+            }
+        });
+
+        Self { pool, healthy }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+}
+
+/// Whether a request should see soft-deleted rows. `?include_deleted=true` is
+/// rejected with 403 unless the request carries `Authorization: Bearer
+/// <ADMIN_TOKEN>`.
+pub struct IncludeDeleted(pub bool);
+
+#[async_trait]
+impl FromRequestParts<AppState> for IncludeDeleted {
+    type Rejection = (StatusCode, Json<ApiError>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let Query(params) = Query::<VisibilityParams>::try_from_uri(&parts.uri).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+        if !params.include_deleted.unwrap_or(false) {
+            return Ok(Self(false));
+        }
+
+        if !is_admin(&parts.headers, &state.config) {
+            return Err((
+                StatusCode::FORBIDDEN,
+                Json(ApiError {
+                    error: "include_deleted requires an admin token".to_string(),
+                }),
+            ));
+        }
+        Ok(Self(true))
+    }
+}
+
+/// Guards admin-only endpoints: rejects the request with 403 unless it
+/// carries `Authorization: Bearer <ADMIN_TOKEN>`.
+pub struct Admin;
+
+#[async_trait]
+impl FromRequestParts<AppState> for Admin {
+    type Rejection = (StatusCode, Json<ApiError>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if !is_admin(&parts.headers, &state.config) {
+            return Err((
+                StatusCode::FORBIDDEN,
+                Json(ApiError {
+                    error: "This endpoint requires an admin token".to_string(),
+                }),
+            ));
+        }
+        Ok(Self)
+    }
+}
+
+/// Whether the request's bearer token matches the configured admin token.
+fn is_admin(headers: &HeaderMap, config: &AppConfig) -> bool {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    matches!(
+        (&config.admin_token, bearer),
+        (Some(token), Some(bearer)) if token == bearer
+    )
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Ranking policy for a paper's implementations: official repos first, then
+/// by stars, with maintained (not archived or disabled) repos ahead of equally
+/// starred ones. `created_at` keeps ties stable.
+const IMPLEMENTATION_RANKING: &str = "COALESCE(is_official, FALSE) DESC, \
+     stars DESC NULLS LAST, \
+     (COALESCE(archived, FALSE) OR COALESCE(disabled, FALSE)), created_at";
+
+/// Order of papers sharing an arXiv base ID, canonical row first: live rows
+/// before soft-deleted ones, then highest `vN`, with unversioned rows after
+/// explicit versions and the newest row winning ties.
+const ARXIV_LATEST_FIRST: &str =
+    "deleted_at IS NOT NULL, arxiv_version DESC NULLS LAST, created_at DESC, id";
+
+/// SQL condition on `papers v` matching paper `$1` and its other arXiv
+/// versions, whose code, languages and ORCIDs count as the paper's own.
+const SAME_ARXIV_PAPER: &str =
+    "(v.id = $1 OR v.arxiv_base_id = (SELECT p.arxiv_base_id FROM papers p WHERE p.id = $1))";
+
+/// Postgres `NOTIFY` channel carrying a JSON [`PaperEvent`] for every paper
+/// the arXiv listener inserts.
+pub const PAPER_EVENTS_CHANNEL: &str = "paper_events";
+
+/// Record a finished ingestion job in `ingestion_runs`, which `/api/stats`
+/// reports as data freshness. `source` groups related jobs, e.g. both GitHub
+/// scrapers record `github`.
+pub async fn record_ingestion_run(
+    pool: &Pool<Postgres>,
+    source: &str,
+    started_at: chrono::DateTime<chrono::Utc>,
+    items_processed: i64,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO ingestion_runs (source, started_at, items_processed) VALUES ($1, $2, $3)",
+    )
+    .bind(source)
+    .bind(started_at)
+    .bind(items_processed)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Strip an `arXiv:` prefix and `vN` suffix from an arXiv ID, matching the
+/// `papers.arxiv_base_id` column (`arXiv:2301.00001v2` -> `2301.00001`).
+pub fn arxiv_base_id(arxiv_id: &str) -> &str {
+    let id = arxiv_id.trim();
+    let id = id
+        .get(..6)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("arxiv:"))
+        .map_or(id, |_| &id[6..]);
+
+    match id.rfind('v') {
+        Some(pos) if pos + 1 < id.len() && id[pos + 1..].bytes().all(|b| b.is_ascii_digit()) => {
+            &id[..pos]
+        }
+        _ => id,
+    }
+}
+
+/// Lowercase `owner/repo` of a GitHub URL, matching the
+/// `implementations.github_repo` column
+/// (`https://github.com/Owner/Repo.git` -> `owner/repo`).
+pub fn github_repo(github_url: &str) -> Option<String> {
+    let (_, path) = github_url.split_once("github.com/")?;
+    let mut parts = path.split(['/', '?', '#']);
+    let owner = parts.next().filter(|owner| !owner.is_empty())?;
+    let repo = parts.next().filter(|repo| !repo.is_empty())?;
+    let repo = repo.strip_suffix(".git").unwrap_or(repo);
+
+    Some(format!("{}/{}", owner, repo).to_lowercase())
+}
+
+/// Normalize a Python package name per PEP 503 (`Foo_Bar.baz` -> `foo-bar-baz`).
+pub fn normalize_package_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+static DOI: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"^10\.\d{4,9}/\S+$").unwrap());
+
+/// Normalize a DOI for storage and lookup: strip resolver prefixes
+/// (`https://doi.org/`, `doi:`) and lowercase it. Returns `None` if the
+/// input is not a DOI.
+pub fn normalize_doi(input: &str) -> Option<String> {
+    let doi = input.trim().to_lowercase();
+    let doi = [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "http://dx.doi.org/",
+        "doi:",
+    ]
+    .iter()
+    .find_map(|prefix| doi.strip_prefix(prefix))
+    .unwrap_or(&doi)
+    .trim();
+
+    DOI.is_match(doi).then(|| doi.to_string())
+}
+
+/// Normalize an ORCID iD (`https://orcid.org/0000-0002-1825-0097` or
+/// `0000000218250097`) to its hyphenated form, checking the ISO 7064
+/// MOD 11-2 check digit. Returns `None` if the iD is invalid.
+pub fn normalize_orcid(input: &str) -> Option<String> {
+    let input = input.trim();
+    let input = input
+        .strip_prefix("https://orcid.org/")
+        .or_else(|| input.strip_prefix("http://orcid.org/"))
+        .unwrap_or(input);
+
+    let chars: Vec<char> = input
+        .chars()
+        .filter(|c| *c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    if chars.len() != 16 || !chars[..15].iter().all(char::is_ascii_digit) {
+        return None;
+    }
+
+    let total = chars[..15]
+        .iter()
+        .fold(0u32, |total, c| (total + c.to_digit(10).unwrap()) * 2);
+    let check = (12 - total % 11) % 11;
+    let expected = if check == 10 {
+        'X'
+    } else {
+        char::from_digit(check, 10).unwrap()
+    };
+    if chars[15] != expected {
+        return None;
+    }
+
+    let digits: String = chars.into_iter().collect();
+    Some(format!(
+        "{}-{}-{}-{}",
+        &digits[0..4],
+        &digits[4..8],
+        &digits[8..12],
+        &digits[12..16]
+    ))
 }
 
 // ============================================================================
 // Router Setup
 // ============================================================================
 
-pub fn create_app(pool: Pool<Postgres>, search_index: Option<Arc<search::SearchIndex>>) -> Router {
+/// The API on a Postgres database.
+pub fn create_app(
+    pool: Pool<Postgres>,
+    search_index: Option<Arc<search::SearchIndex>>,
+    paper_store: Option<Arc<storage::PaperStore>>,
+    config: AppConfig,
+) -> Router {
+    let repository = Arc::new(postgres::PgRepository::new(pool, None));
+    create_app_with_repository(repository, search_index, paper_store, config)
+}
+
+/// The API on any [`Repository`], e.g. Postgres with a read replica or SQLite.
+pub fn create_app_with_repository(
+    repository: Arc<dyn Repository>,
+    search_index: Option<Arc<search::SearchIndex>>,
+    paper_store: Option<Arc<storage::PaperStore>>,
+    config: AppConfig,
+) -> Router {
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
         .allow_headers(Any);
 
-    let state = AppState { pool, search_index };
+    let state = AppState {
+        repository,
+        search_index,
+        paper_store,
+        config,
+    };
 
     Router::new()
         // Health & Stats
         .route("/", get(root))
         .route("/api/health", get(health_check))
         .route("/api/stats", get(get_stats))
+        // Search
+        .route("/api/search/status", get(get_search_status))
+        .route("/api/admin/search/optimize", post(optimize_search_index))
         // Papers
         .route("/api/papers", get(get_papers))
-        .route("/api/papers/{id}", get(get_paper_by_id))
+        .route("/api/papers/stream", get(stream_paper_events))
+        .route("/api/papers/:id", get(get_paper_by_id))
+        .route("/api/papers/by-doi/*doi", get(get_paper_by_doi))
+        .route("/api/papers/by-arxiv/*arxiv_id", get(get_paper_by_arxiv_id))
+        .route("/api/papers/:id/implementations", get(get_paper_implementations))
+        .route("/api/papers/:id/pdf", get(get_paper_pdf))
+        .route("/api/papers/:id/thumbnail", get(get_paper_thumbnail))
+        // Authors
+        .route("/api/authors/orcid/:orcid", get(get_author_by_orcid))
         // Datasets
         .route("/api/datasets", get(get_datasets))
-        .route("/api/datasets/{id}", get(get_dataset_by_id))
+        .route("/api/datasets/:id", get(get_dataset_by_id))
+        .route("/api/datasets/:id/files", get(get_dataset_files))
+        .route("/api/datasets/:id/files/sha256sums", get(get_dataset_checksums))
         // Benchmarks
         .route("/api/benchmarks", get(get_benchmarks))
-        .route("/api/benchmarks/{id}", get(get_benchmark_by_id))
+        .route("/api/benchmarks/:id", get(get_benchmark_by_id))
         // Implementations
         .route("/api/implementations", get(get_implementations))
-        .route("/api/implementations/{id}", get(get_implementation_by_id))
+        .route("/api/implementations/:id", get(get_implementation_by_id))
+        .route("/api/dependencies/:name/papers", get(get_papers_by_dependency))
+        // Tasks
+        .route("/api/tasks", get(get_task_tree))
+        .route("/api/tasks/:id", get(get_task_by_id))
+        .route("/api/admin/tasks/:id/parent", put(set_task_parent))
         // Benchmark Results
         .route("/api/benchmark-results", get(get_benchmark_results))
+        // Badges (wildcard so old-style IDs like cs.CV/0601001 work)
+        .route("/api/badge/paper/*arxiv_id", get(get_paper_badge))
+        // Webhooks
+        .route("/api/webhooks/github", post(github_webhook))
         .layer(cors)
         .with_state(state)
 }
@@ -215,44 +835,84 @@ async fn health_check() -> Json<Message> {
 async fn get_stats(
     State(state): State<AppState>,
 ) -> Result<Json<StatsResponse>, (StatusCode, Json<ApiError>)> {
-    let papers_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM papers")
-        .fetch_one(&state.pool)
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiError {
-                    error: e.to_string(),
-                }),
-            )
-        })?;
+    let counts = state.repository.catalog_counts().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                error: e.to_string(),
+            }),
+        )
+    })?;
 
-    let datasets_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM datasets")
-        .fetch_one(&state.pool)
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiError {
-                    error: e.to_string(),
-                }),
-            )
-        })?;
+    let last_ingested_at = state.repository.last_ingested_at().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                error: e.to_string(),
+            }),
+        )
+    })?;
 
-    let benchmarks_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM benchmarks")
-        .fetch_one(&state.pool)
-        .await
+    let papers_with_code_percent = if counts.papers > 0 {
+        (counts.papers_with_code as f64 * 1000.0 / counts.papers as f64).round() / 10.0
+    } else {
+        0.0
+    };
+
+    Ok(Json(StatsResponse {
+        papers_count: counts.papers,
+        datasets_count: counts.datasets,
+        benchmarks_count: counts.benchmarks,
+        implementations_count: counts.implementations,
+        results_count: counts.results,
+        papers_with_code_count: counts.papers_with_code,
+        papers_with_code_percent,
+        last_ingested_at: last_ingested_at.into_iter().collect(),
+    }))
+}
+
+// ============================================================================
+// Handlers: Search
+// ============================================================================
+
+async fn get_search_status(
+    State(state): State<AppState>,
+) -> Result<Json<SearchStatus>, (StatusCode, Json<ApiError>)> {
+    let index = state
+        .search_index
+        .as_ref()
+        .map(|search_index| search_index.status())
+        .transpose()
         .map_err(|e| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiError {
-                    error: e.to_string(),
+                    error: format!("{:#}", e),
                 }),
             )
         })?;
 
-    let implementations_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM implementations")
-        .fetch_one(&state.pool)
+    Ok(Json(SearchStatus {
+        available: index.is_some(),
+        index,
+    }))
+}
+
+/// Merge the search index into a single segment (admin only).
+async fn optimize_search_index(
+    State(state): State<AppState>,
+    _admin: Admin,
+) -> Result<Json<search::IndexStatus>, (StatusCode, Json<ApiError>)> {
+    let search_index = state.search_index.clone().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError {
+                error: "Search index is not loaded".to_string(),
+            }),
+        )
+    })?;
+
+    let result = tokio::task::spawn_blocking(move || search_index.optimize())
         .await
         .map_err(|e| {
             (
@@ -263,12 +923,19 @@ async fn get_stats(
             )
         })?;
 
-    Ok(Json(StatsResponse {
-        papers_count: papers_count.0,
-        datasets_count: datasets_count.0,
-        benchmarks_count: benchmarks_count.0,
-        implementations_count: implementations_count.0,
-    }))
+    result.map(Json).map_err(|e| {
+        // Another process (e.g. build_search_index) is writing to the index
+        let status = match e.downcast_ref::<tantivy::TantivyError>() {
+            Some(tantivy::TantivyError::LockFailure(..)) => StatusCode::CONFLICT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        (
+            status,
+            Json(ApiError {
+                error: format!("{:#}", e),
+            }),
+        )
+    })
 }
 
 // ============================================================================
@@ -277,91 +944,166 @@ async fn get_stats(
 
 async fn get_papers(
     State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
     Query(params): Query<search::SearchParams>,
 ) -> Result<Json<search::SearchResponse<Paper>>, (StatusCode, Json<ApiError>)> {
     let limit = params.limit.unwrap_or(20).min(100) as usize;
     let offset = params.offset.unwrap_or(0) as usize;
-    let order = if params.order.as_deref() == Some("asc") {
-        "ASC"
-    } else {
-        "DESC"
-    };
 
-    // If search query provided and Tantivy index available, use full-text search
-    if let Some(query_str) = params.get_query() {
-        if !query_str.trim().is_empty() {
-            if let Some(ref search_index) = state.search_index {
-                return search_papers_tantivy(&state, search_index, query_str, &params, limit, offset).await;
-            }
-            // Fall back to PostgreSQL ILIKE if no Tantivy index
-            return search_papers_postgres(&state, query_str, &params, limit, offset, order).await;
+    let query_str = params.get_query().filter(|q| !q.trim().is_empty());
+
+    let Json(mut response) = match (query_str, &state.search_index) {
+        // If search query provided and Tantivy index available, use full-text search
+        (Some(query_str), Some(search_index)) => {
+            search_papers_tantivy(
+                &state,
+                search_index,
+                query_str,
+                &params,
+                limit,
+                offset,
+                include_deleted,
+            )
+            .await?
         }
+        // Otherwise search (case-insensitive substring) or browse the database
+        (query_str, _) => {
+            let query = PaperQuery {
+                search: query_str.map(str::to_string),
+                has_code: params.has_code,
+                include_deleted,
+                ascending: params.order.as_deref() == Some("asc"),
+                limit: limit as i64,
+                offset: offset as i64,
+            };
+            list_papers(&state, &query).await?
+        }
+    };
+
+    attach_versions(&state, &mut response.papers, include_deleted).await?;
+    if let Some(lang) = &params.lang {
+        attach_translations(&state, &mut response.papers, lang).await?;
     }
 
-    // No search query - browse papers from PostgreSQL
-    browse_papers_postgres(&state, limit, offset, order).await
+    Ok(Json(response))
 }
 
-/// Search papers using Tantivy full-text search
-async fn search_papers_tantivy(
+/// Fill in each arXiv paper's `versions` from the rows sharing its base ID.
+async fn attach_versions(
     state: &AppState,
-    search_index: &search::SearchIndex,
-    query_str: &str,
-    params: &search::SearchParams,
-    limit: usize,
-    offset: usize,
-) -> Result<Json<search::SearchResponse<Paper>>, (StatusCode, Json<ApiError>)> {
-    // Execute Tantivy search
-    let search_result = search::query::search_papers(search_index, query_str, params, limit, offset)
+    papers: &mut [Paper],
+    include_deleted: bool,
+) -> Result<(), (StatusCode, Json<ApiError>)> {
+    let base_ids: Vec<String> = papers
+        .iter()
+        .filter_map(|p| p.arxiv_id.as_deref())
+        .map(|id| arxiv_base_id(id).to_string())
+        .collect();
+    if base_ids.is_empty() {
+        return Ok(());
+    }
+
+    let versions = state
+        .repository
+        .arxiv_versions(&base_ids, include_deleted)
+        .await
         .map_err(|e| {
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiError {
-                    error: format!("Search failed: {}", e),
+                    error: e.to_string(),
                 }),
             )
         })?;
 
-    if search_result.paper_ids.is_empty() {
-        return Ok(Json(search::SearchResponse {
-            papers: vec![],
-            total_hits: 0,
-            facets: search_result.facets,
-        }));
+    for paper in papers.iter_mut() {
+        let Some(base_id) = paper.arxiv_id.as_deref().map(arxiv_base_id) else {
+            continue;
+        };
+        paper.versions = versions
+            .iter()
+            .filter(|v| v.arxiv_base_id == base_id)
+            .cloned()
+            .collect();
     }
 
-    // Fetch full paper data from PostgreSQL, preserving search order
-    let papers = fetch_papers_by_ids(&state.pool, &search_result.paper_ids).await?;
+    Ok(())
+}
 
-    Ok(Json(search::SearchResponse {
-        papers,
-        total_hits: search_result.total_hits,
+/// Fill in each paper's `translation` for the given language, where one exists.
+async fn attach_translations(
+    state: &AppState,
+    papers: &mut [Paper],
+    lang: &str,
+) -> Result<(), (StatusCode, Json<ApiError>)> {
+    let ids: Vec<uuid::Uuid> = papers.iter().map(|p| p.id).collect();
+
+    let translations = state.repository.translations(&ids, lang).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                error: e.to_string(),
+            }),
+        )
+    })?;
+
+    for translation in translations {
+        if let Some(paper) = papers.iter_mut().find(|p| p.id == translation.paper_id) {
+            paper.translation = Some(translation);
+        }
+    }
+
+    Ok(())
+}
+
+/// Search papers using Tantivy full-text search
+async fn search_papers_tantivy(
+    state: &AppState,
+    search_index: &search::SearchIndex,
+    query_str: &str,
+    params: &search::SearchParams,
+    limit: usize,
+    offset: usize,
+    include_deleted: bool,
+) -> Result<Json<search::SearchResponse<Paper>>, (StatusCode, Json<ApiError>)> {
+    // Execute Tantivy search
+    let search_result = search::query::search_papers(search_index, query_str, params, limit, offset)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: format!("Search failed: {}", e),
+                }),
+            )
+        })?;
+
+    if search_result.paper_ids.is_empty() {
+        return Ok(Json(search::SearchResponse {
+            papers: vec![],
+            total_hits: 0,
+            facets: search_result.facets,
+        }));
+    }
+
+    // Fetch full paper data from the database, preserving search order
+    let paper_ids = latest_version_ids(state, &search_result.paper_ids).await?;
+    let papers = fetch_papers_by_ids(state, &paper_ids, include_deleted).await?;
+
+    Ok(Json(search::SearchResponse {
+        papers,
+        total_hits: search_result.total_hits,
         facets: search_result.facets,
     }))
 }
 
-/// Fetch papers by IDs from PostgreSQL, preserving order
-async fn fetch_papers_by_ids(
-    pool: &Pool<Postgres>,
+/// Replace each paper ID with its latest arXiv version's ID, dropping repeats
+/// so a paper matched through several versions appears once, in the position
+/// of its best-ranked hit.
+async fn latest_version_ids(
+    state: &AppState,
     ids: &[uuid::Uuid],
-) -> Result<Vec<Paper>, (StatusCode, Json<ApiError>)> {
-    if ids.is_empty() {
-        return Ok(vec![]);
-    }
-
-    // Fetch all papers by IDs
-    let papers: Vec<Paper> = sqlx::query_as(
-        r#"
-        SELECT id, title, abstract, arxiv_id, arxiv_url, pdf_url,
-               published_date, authors, created_at, updated_at
-        FROM papers
-        WHERE id = ANY($1)
-        "#,
-    )
-    .bind(ids)
-    .fetch_all(pool)
-    .await
-    .map_err(|e| {
+) -> Result<Vec<uuid::Uuid>, (StatusCode, Json<ApiError>)> {
+    let latest = state.repository.latest_version_ids(ids).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiError {
@@ -370,6 +1112,40 @@ async fn fetch_papers_by_ids(
         )
     })?;
 
+    let latest: std::collections::HashMap<uuid::Uuid, uuid::Uuid> = latest.into_iter().collect();
+    let mut seen = std::collections::HashSet::new();
+
+    Ok(ids
+        .iter()
+        .filter_map(|id| latest.get(id).copied())
+        .filter(|id| seen.insert(*id))
+        .collect())
+}
+
+/// Fetch papers by IDs from the database, preserving order
+async fn fetch_papers_by_ids(
+    state: &AppState,
+    ids: &[uuid::Uuid],
+    include_deleted: bool,
+) -> Result<Vec<Paper>, (StatusCode, Json<ApiError>)> {
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    // Fetch all papers by IDs
+    let papers = state
+        .repository
+        .papers_by_ids(ids, include_deleted)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
     // Reorder to match search result order
     let paper_map: std::collections::HashMap<uuid::Uuid, Paper> =
         papers.into_iter().map(|p| (p.id, p)).collect();
@@ -382,34 +1158,13 @@ async fn fetch_papers_by_ids(
     Ok(ordered_papers)
 }
 
-/// Search papers using PostgreSQL ILIKE (fallback)
-async fn search_papers_postgres(
+/// Search (case-insensitive substring, without Tantivy) or browse papers
+/// in the database.
+async fn list_papers(
     state: &AppState,
-    query_str: &str,
-    _params: &search::SearchParams,
-    limit: usize,
-    offset: usize,
-    order: &str,
+    query: &PaperQuery,
 ) -> Result<Json<search::SearchResponse<Paper>>, (StatusCode, Json<ApiError>)> {
-    let search_pattern = format!("%{}%", query_str);
-
-    let papers: Vec<Paper> = sqlx::query_as(&format!(
-        r#"
-        SELECT id, title, abstract, arxiv_id, arxiv_url, pdf_url,
-               published_date, authors, created_at, updated_at
-        FROM papers
-        WHERE title ILIKE $1 OR abstract ILIKE $1
-        ORDER BY published_date {} NULLS LAST
-        LIMIT $2 OFFSET $3
-        "#,
-        order
-    ))
-    .bind(&search_pattern)
-    .bind(limit as i64)
-    .bind(offset as i64)
-    .fetch_all(&state.pool)
-    .await
-    .map_err(|e| {
+    let papers = state.repository.papers(query).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiError {
@@ -418,35 +1173,612 @@ async fn search_papers_postgres(
         )
     })?;
 
+    // The database search doesn't provide a total count
+    let total = if query.search.is_some() { 0 } else { papers.len() };
     Ok(Json(search::SearchResponse {
         papers,
-        total_hits: 0, // PostgreSQL fallback doesn't provide total count
+        total_hits: total,
         facets: None,
     }))
 }
 
-/// Browse papers without search (PostgreSQL)
-async fn browse_papers_postgres(
-    state: &AppState,
-    limit: usize,
-    offset: usize,
-    order: &str,
-) -> Result<Json<search::SearchResponse<Paper>>, (StatusCode, Json<ApiError>)> {
-    let papers: Vec<Paper> = sqlx::query_as(&format!(
-        r#"
-        SELECT id, title, abstract, arxiv_id, arxiv_url, pdf_url,
-               published_date, authors, created_at, updated_at
-        FROM papers
-        ORDER BY published_date {} NULLS LAST
-        LIMIT $1 OFFSET $2
-        "#,
-        order
-    ))
-    .bind(limit as i64)
-    .bind(offset as i64)
-    .fetch_all(&state.pool)
-    .await
-    .map_err(|e| {
+/// SQL condition on `papers` that hides superseded arXiv versions.
+pub fn latest_arxiv_version_filter() -> String {
+    format!(
+        r#"(papers.arxiv_base_id IS NULL OR papers.id = (
+            SELECT v.id FROM papers v
+            WHERE v.arxiv_base_id = papers.arxiv_base_id
+            ORDER BY {}
+            LIMIT 1
+        ))"#,
+        ARXIV_LATEST_FIRST
+    )
+}
+
+/// A paper, or 404 if it doesn't exist.
+async fn find_paper(
+    state: &AppState,
+    id: uuid::Uuid,
+    include_deleted: bool,
+) -> Result<Paper, (StatusCode, Json<ApiError>)> {
+    let paper = state
+        .repository
+        .paper_by_id(id, include_deleted)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    paper.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                error: "Paper not found".to_string(),
+            }),
+        )
+    })
+}
+
+async fn get_paper_by_id(
+    State(state): State<AppState>,
+    Path(id): Path<uuid::Uuid>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Query(params): Query<PaperParams>,
+) -> Result<Json<PaperWithImplementations>, (StatusCode, Json<ApiError>)> {
+    let paper = find_paper(&state, id, include_deleted).await?;
+
+    Ok(Json(paper_details(&state, paper, &params, include_deleted).await?))
+}
+
+async fn get_paper_by_doi(
+    State(state): State<AppState>,
+    Path(doi): Path<String>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Query(params): Query<PaperParams>,
+) -> Result<Json<PaperWithImplementations>, (StatusCode, Json<ApiError>)> {
+    let doi = normalize_doi(&doi).ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError {
+                error: format!("Invalid DOI: '{}'", doi),
+            }),
+        )
+    })?;
+
+    let paper = state
+        .repository
+        .paper_by_doi(&doi, include_deleted)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    let paper = paper.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                error: "Paper not found".to_string(),
+            }),
+        )
+    })?;
+
+    Ok(Json(paper_details(&state, paper, &params, include_deleted).await?))
+}
+
+/// Look a paper up by arXiv ID, with or without a version suffix. Always
+/// returns the latest loaded version; the others are listed in `versions`.
+async fn get_paper_by_arxiv_id(
+    State(state): State<AppState>,
+    Path(arxiv_id): Path<String>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Query(params): Query<PaperParams>,
+) -> Result<Json<PaperWithImplementations>, (StatusCode, Json<ApiError>)> {
+    let paper = state
+        .repository
+        .paper_by_arxiv_base_id(arxiv_base_id(&arxiv_id), include_deleted)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    let paper = paper.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                error: "Paper not found".to_string(),
+            }),
+        )
+    })?;
+
+    Ok(Json(paper_details(&state, paper, &params, include_deleted).await?))
+}
+
+/// Attach implementations, language breakdown and author ORCIDs, gathered
+/// across the paper's arXiv versions, plus the versions and translation.
+async fn paper_details(
+    state: &AppState,
+    mut paper: Paper,
+    params: &PaperParams,
+    include_deleted: bool,
+) -> Result<PaperWithImplementations, (StatusCode, Json<ApiError>)> {
+    let id = paper.id;
+
+    attach_versions(state, std::slice::from_mut(&mut paper), include_deleted).await?;
+    if let Some(lang) = &params.lang {
+        attach_translations(state, std::slice::from_mut(&mut paper), lang).await?;
+    }
+
+    let implementations = state
+        .repository
+        .paper_implementations(id, include_deleted)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    let languages = state
+        .repository
+        .paper_languages(id, include_deleted)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    let author_orcids = state.repository.paper_author_orcids(id).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                error: e.to_string(),
+            }),
+        )
+    })?;
+
+    Ok(PaperWithImplementations {
+        paper,
+        implementations: implementations
+            .into_iter()
+            .map(|ranked| ranked.implementation)
+            .collect(),
+        languages,
+        author_orcids,
+    })
+}
+
+async fn get_paper_implementations(
+    State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<Vec<RankedImplementation>>, (StatusCode, Json<ApiError>)> {
+    find_paper(&state, id, include_deleted).await?;
+
+    let implementations = state
+        .repository
+        .paper_implementations(id, include_deleted)
+        .await;
+
+    implementations.map(Json).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                error: e.to_string(),
+            }),
+        )
+    })
+}
+
+/// Serve a paper's PDF from the paper store, downloading it on first request.
+/// Range requests are supported so viewers can page through large PDFs.
+async fn get_paper_pdf(
+    State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Path(id): Path<uuid::Uuid>,
+    request: Request,
+) -> Result<Response, (StatusCode, Json<ApiError>)> {
+    let Paper { pdf_url, arxiv_id, .. } = find_paper(&state, id, include_deleted).await?;
+
+    let upstream_url = storage::pdf_source_url(pdf_url, arxiv_id.as_deref()).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                error: "No PDF available for this paper".to_string(),
+            }),
+        )
+    })?;
+
+    // Without a paper store, send the client straight to the upstream PDF
+    let Some(paper_store) = &state.paper_store else {
+        return Ok(Redirect::temporary(&upstream_url).into_response());
+    };
+
+    let pdf_path = paper_store
+        .fetch_pdf(id, &upstream_url)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(ApiError {
+                    error: format!("Failed to fetch PDF: {:#}", e),
+                }),
+            )
+        })?;
+
+    ServeFile::new(pdf_path)
+        .try_call(request)
+        .await
+        .map(IntoResponse::into_response)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })
+}
+
+/// Serve a paper's first-page thumbnail, as rendered by `thumbnail_worker`.
+async fn get_paper_thumbnail(
+    State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Path(id): Path<uuid::Uuid>,
+    request: Request,
+) -> Result<Response, (StatusCode, Json<ApiError>)> {
+    find_paper(&state, id, include_deleted).await?;
+
+    let thumbnail_path = state
+        .paper_store
+        .as_ref()
+        .map(|paper_store| paper_store.thumbnail_path(id))
+        .filter(|path| path.is_file())
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ApiError {
+                    error: "Thumbnail not available".to_string(),
+                }),
+            )
+        })?;
+
+    ServeFile::new(thumbnail_path)
+        .try_call(request)
+        .await
+        .map(IntoResponse::into_response)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })
+}
+
+// ============================================================================
+// Handlers: Authors
+// ============================================================================
+
+async fn get_author_by_orcid(
+    State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Path(orcid): Path<String>,
+    Query(params): Query<PaginationParams>,
+) -> Result<Json<AuthorWithPapers>, (StatusCode, Json<ApiError>)> {
+    let orcid = normalize_orcid(&orcid).ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiError {
+                error: format!("Invalid ORCID iD: '{}'", orcid),
+            }),
+        )
+    })?;
+    let limit = params.limit.unwrap_or(20).min(100);
+    let offset = params.offset.unwrap_or(0);
+
+    let name = state
+        .repository
+        .author_name(&orcid, include_deleted)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    let name = name.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                error: "No papers found for this ORCID iD".to_string(),
+            }),
+        )
+    })?;
+
+    // Each paper once, as its latest arXiv version, wherever the ORCID was found
+    let papers = state
+        .repository
+        .author_papers(&orcid, include_deleted, limit, offset)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    Ok(Json(AuthorWithPapers {
+        orcid,
+        name,
+        papers,
+    }))
+}
+
+// ============================================================================
+// Handlers: Datasets
+// ============================================================================
+
+async fn get_datasets(
+    State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Query(params): Query<PaginationParams>,
+) -> Result<Json<Vec<Dataset>>, (StatusCode, Json<ApiError>)> {
+    let limit = params.limit.unwrap_or(20).min(100);
+    let offset = params.offset.unwrap_or(0);
+
+    let datasets = state
+        .repository
+        .datasets(params.search.as_deref(), include_deleted, limit, offset)
+        .await;
+
+    datasets.map(Json).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                error: e.to_string(),
+            }),
+        )
+    })
+}
+
+/// A dataset, or 404 if it doesn't exist.
+async fn find_dataset(
+    state: &AppState,
+    id: uuid::Uuid,
+    include_deleted: bool,
+) -> Result<Dataset, (StatusCode, Json<ApiError>)> {
+    let dataset = state
+        .repository
+        .dataset_by_id(id, include_deleted)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    dataset.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                error: "Dataset not found".to_string(),
+            }),
+        )
+    })
+}
+
+async fn get_dataset_by_id(
+    State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<Dataset>, (StatusCode, Json<ApiError>)> {
+    find_dataset(&state, id, include_deleted).await.map(Json)
+}
+
+async fn fetch_dataset_files(
+    state: &AppState,
+    dataset_id: uuid::Uuid,
+    include_deleted: bool,
+) -> Result<Vec<DatasetFile>, (StatusCode, Json<ApiError>)> {
+    find_dataset(state, dataset_id, include_deleted).await?;
+
+    state.repository.dataset_files(dataset_id).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                error: e.to_string(),
+            }),
+        )
+    })
+}
+
+async fn get_dataset_files(
+    State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<Vec<DatasetFile>>, (StatusCode, Json<ApiError>)> {
+    fetch_dataset_files(&state, id, include_deleted)
+        .await
+        .map(Json)
+}
+
+/// Checksums of a dataset's files in `sha256sum` format, so a download
+/// directory can be verified with `sha256sum -c`. Files without a known
+/// checksum are left out, and mirrors of the same file are listed once.
+async fn get_dataset_checksums(
+    State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<String, (StatusCode, Json<ApiError>)> {
+    let files = fetch_dataset_files(&state, id, include_deleted).await?;
+
+    let mut entries = BTreeSet::new();
+    for file in &files {
+        let Some(sha256) = &file.sha256 else {
+            continue;
+        };
+        let path = file.url.split(['?', '#']).next().unwrap_or_default();
+        let filename = path.rsplit('/').next().unwrap_or_default();
+        if !filename.is_empty() {
+            entries.insert((filename, sha256));
+        }
+    }
+
+    Ok(entries
+        .into_iter()
+        .map(|(filename, sha256)| format!("{}  {}\n", sha256, filename))
+        .collect())
+}
+
+// ============================================================================
+// Handlers: Benchmarks
+// ============================================================================
+
+async fn get_benchmarks(
+    State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Query(params): Query<PaginationParams>,
+) -> Result<Json<Vec<Benchmark>>, (StatusCode, Json<ApiError>)> {
+    let limit = params.limit.unwrap_or(20).min(100);
+    let offset = params.offset.unwrap_or(0);
+
+    let benchmarks = state
+        .repository
+        .benchmarks(params.search.as_deref(), include_deleted, limit, offset)
+        .await;
+
+    benchmarks.map(Json).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                error: e.to_string(),
+            }),
+        )
+    })
+}
+
+async fn get_benchmark_by_id(
+    State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<BenchmarkWithDataset>, (StatusCode, Json<ApiError>)> {
+    let benchmark = state
+        .repository
+        .benchmark_by_id(id, include_deleted)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    let benchmark = benchmark.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                error: "Benchmark not found".to_string(),
+            }),
+        )
+    })?;
+
+    let dataset = if let Some(dataset_id) = benchmark.dataset_id {
+        state
+            .repository
+            .dataset_by_id(dataset_id, include_deleted)
+            .await
+            .ok()
+            .flatten()
+    } else {
+        None
+    };
+
+    Ok(Json(BenchmarkWithDataset { benchmark, dataset }))
+}
+
+// ============================================================================
+// Handlers: Tasks
+// ============================================================================
+
+/// Tasks with rolled-up counts, limited to the subtree under `root` if given.
+async fn fetch_task_nodes(
+    repository: &dyn Repository,
+    root: Option<uuid::Uuid>,
+) -> Result<Vec<TaskNode>, (StatusCode, Json<ApiError>)> {
+    repository.task_nodes(root).await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiError {
+                error: e.to_string(),
+            }),
+        )
+    })
+}
+
+fn attach_subtasks(node: &mut TaskNode, by_parent: &mut HashMap<uuid::Uuid, Vec<TaskNode>>) {
+    if let Some(mut children) = by_parent.remove(&node.id) {
+        for child in &mut children {
+            attach_subtasks(child, by_parent);
+        }
+        node.children = children;
+    }
+}
+
+/// The task with the given ID and its subtasks, or `None` if it doesn't exist.
+async fn load_task(
+    repository: &dyn Repository,
+    id: uuid::Uuid,
+) -> Result<Option<TaskNode>, (StatusCode, Json<ApiError>)> {
+    let nodes = fetch_task_nodes(repository, Some(id)).await?;
+
+    let mut task = None;
+    let mut by_parent: HashMap<uuid::Uuid, Vec<TaskNode>> = HashMap::new();
+    for node in nodes {
+        if node.id == id {
+            task = Some(node);
+        } else if let Some(parent_id) = node.parent_id {
+            by_parent.entry(parent_id).or_default().push(node);
+        }
+    }
+    let Some(mut task) = task else {
+        return Ok(None);
+    };
+    attach_subtasks(&mut task, &mut by_parent);
+
+    task.ancestors = repository.task_ancestors(id).await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiError {
@@ -455,108 +1787,125 @@ async fn browse_papers_postgres(
         )
     })?;
 
-    let total = papers.len();
-    Ok(Json(search::SearchResponse {
-        papers,
-        total_hits: total,
-        facets: None,
-    }))
+    Ok(Some(task))
 }
 
-async fn get_paper_by_id(
+/// The whole taxonomy: top-level tasks, each with its subtasks nested below.
+async fn get_task_tree(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<TaskNode>>, (StatusCode, Json<ApiError>)> {
+    let nodes = fetch_task_nodes(state.repository.as_ref(), None).await?;
+
+    let mut roots = Vec::new();
+    let mut by_parent: HashMap<uuid::Uuid, Vec<TaskNode>> = HashMap::new();
+    for node in nodes {
+        match node.parent_id {
+            Some(parent_id) => by_parent.entry(parent_id).or_default().push(node),
+            None => roots.push(node),
+        }
+    }
+    for root in &mut roots {
+        attach_subtasks(root, &mut by_parent);
+    }
+
+    Ok(Json(roots))
+}
+
+async fn get_task_by_id(
     State(state): State<AppState>,
     Path(id): Path<uuid::Uuid>,
-) -> Result<Json<PaperWithImplementations>, (StatusCode, Json<ApiError>)> {
-    let paper = sqlx::query_as::<_, Paper>(
-        r#"
-        SELECT id, title, abstract, arxiv_id, arxiv_url, pdf_url,
-               published_date, authors, created_at, updated_at
-        FROM papers WHERE id = $1
-        "#,
-    )
-    .bind(id)
-    .fetch_optional(&state.pool)
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
+) -> Result<Json<TaskNode>, (StatusCode, Json<ApiError>)> {
+    load_task(state.repository.as_ref(), id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ApiError {
+                    error: "Task not found".to_string(),
+                }),
+            )
+        })
+}
+
+/// Move a task under another one (by name), or to the top level. Returns
+/// 409 if the new parent is the task itself or one of its subtasks.
+async fn set_task_parent(
+    State(state): State<AppState>,
+    _admin: Admin,
+    Path(id): Path<uuid::Uuid>,
+    Json(update): Json<TaskParentUpdate>,
+) -> Result<Json<TaskNode>, (StatusCode, Json<ApiError>)> {
+    let parent = update.parent.as_deref().map(str::trim);
+    if parent == Some("") {
+        return Err((
+            StatusCode::BAD_REQUEST,
             Json(ApiError {
-                error: e.to_string(),
+                error: "Parent task name cannot be empty".to_string(),
             }),
-        )
-    })?;
+        ));
+    }
 
-    let paper = paper.ok_or_else(|| {
+    let change = state
+        .repository
+        .set_task_parent(id, parent)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    match change {
+        TaskParentChange::Updated => {}
+        TaskParentChange::NotFound => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ApiError {
+                    error: "Task not found".to_string(),
+                }),
+            ));
+        }
+        TaskParentChange::Cycle(message) => {
+            return Err((StatusCode::CONFLICT, Json(ApiError { error: message })));
+        }
+    }
+
+    // Read back from the primary; a replica may not have the change yet
+    let task = load_task(state.repository.primary().as_ref(), id).await?;
+    task.map(Json).ok_or_else(|| {
         (
             StatusCode::NOT_FOUND,
             Json(ApiError {
-                error: "Paper not found".to_string(),
+                error: "Task not found".to_string(),
             }),
         )
-    })?;
-
-    let implementations = sqlx::query_as::<_, Implementation>(
-        r#"
-        SELECT id, paper_id, github_url, framework, stars, is_official, created_at, updated_at
-        FROM implementations WHERE paper_id = $1
-        "#,
-    )
-    .bind(id)
-    .fetch_all(&state.pool)
-    .await
-    .unwrap_or_default();
-
-    Ok(Json(PaperWithImplementations {
-        paper,
-        implementations,
-    }))
+    })
 }
 
 // ============================================================================
-// Handlers: Datasets
+// Handlers: Implementations
 // ============================================================================
 
-async fn get_datasets(
+async fn get_implementations(
     State(state): State<AppState>,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<Vec<Dataset>>, (StatusCode, Json<ApiError>)> {
-    let limit = params.limit.unwrap_or(20).min(100);
-    let offset = params.offset.unwrap_or(0);
-
-    let datasets = if let Some(search) = &params.search {
-        let search_pattern = format!("%{}%", search);
-        sqlx::query_as::<_, Dataset>(
-            r#"
-            SELECT id, name, description, modalities, task_categories, languages,
-                   size, homepage_url, github_url, paper_url, created_at, updated_at
-            FROM datasets
-            WHERE name ILIKE $1 OR description ILIKE $1
-            ORDER BY name
-            LIMIT $2 OFFSET $3
-            "#,
-        )
-        .bind(search_pattern)
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&state.pool)
-        .await
-    } else {
-        sqlx::query_as::<_, Dataset>(
-            r#"
-            SELECT id, name, description, modalities, task_categories, languages,
-                   size, homepage_url, github_url, paper_url, created_at, updated_at
-            FROM datasets
-            ORDER BY name
-            LIMIT $1 OFFSET $2
-            "#,
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&state.pool)
-        .await
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Query(params): Query<ImplementationParams>,
+) -> Result<Json<Vec<Implementation>>, (StatusCode, Json<ApiError>)> {
+    let query = ImplementationQuery {
+        reproducible: params.reproducible,
+        archived: params.archived,
+        include_deleted,
+        limit: params.limit.unwrap_or(20).min(100),
+        offset: params.offset.unwrap_or(0),
     };
 
-    datasets.map(Json).map_err(|e| {
+    let implementations = state.repository.implementations(&query).await;
+
+    implementations.map(Json).map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiError {
@@ -566,82 +1915,112 @@ async fn get_datasets(
     })
 }
 
-async fn get_dataset_by_id(
+async fn get_implementation_by_id(
     State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
     Path(id): Path<uuid::Uuid>,
-) -> Result<Json<Dataset>, (StatusCode, Json<ApiError>)> {
-    let dataset = sqlx::query_as::<_, Dataset>(
-        r#"
-        SELECT id, name, description, modalities, task_categories, languages,
-               size, homepage_url, github_url, paper_url, created_at, updated_at
-        FROM datasets WHERE id = $1
-        "#,
-    )
-    .bind(id)
-    .fetch_optional(&state.pool)
-    .await
-    .map_err(|e| {
+) -> Result<Json<ImplementationDetail>, (StatusCode, Json<ApiError>)> {
+    let implementation = state
+        .repository
+        .implementation_by_id(id, include_deleted)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    let implementation = implementation.ok_or_else(|| {
         (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::NOT_FOUND,
             Json(ApiError {
-                error: e.to_string(),
+                error: "Implementation not found".to_string(),
             }),
         )
     })?;
 
-    dataset.map(Json).ok_or_else(|| {
+    let packages = state
+        .repository
+        .implementation_packages(id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    let dependencies = state
+        .repository
+        .implementation_dependencies(id)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    Ok(Json(ImplementationDetail {
+        implementation,
+        packages,
+        dependencies,
+    }))
+}
+
+/// Papers whose implementations declare a dependency on the given package,
+/// e.g. `/api/dependencies/diffusers/papers?official=true`.
+async fn get_papers_by_dependency(
+    State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
+    Path(name): Path<String>,
+    Query(params): Query<DependencyParams>,
+) -> Result<Json<Vec<PaperSummary>>, (StatusCode, Json<ApiError>)> {
+    let query = DependencyQuery {
+        name: normalize_package_name(&name),
+        official: params.official,
+        include_deleted,
+        limit: params.limit.unwrap_or(20).min(100),
+        offset: params.offset.unwrap_or(0),
+    };
+
+    let papers = state.repository.papers_by_dependency(&query).await;
+
+    papers.map(Json).map_err(|e| {
         (
-            StatusCode::NOT_FOUND,
+            StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiError {
-                error: "Dataset not found".to_string(),
+                error: e.to_string(),
             }),
         )
     })
 }
 
 // ============================================================================
-// Handlers: Benchmarks
+// Handlers: Benchmark Results
 // ============================================================================
 
-async fn get_benchmarks(
+async fn get_benchmark_results(
     State(state): State<AppState>,
+    IncludeDeleted(include_deleted): IncludeDeleted,
     Query(params): Query<PaginationParams>,
-) -> Result<Json<Vec<Benchmark>>, (StatusCode, Json<ApiError>)> {
+) -> Result<Json<Vec<BenchmarkResult>>, (StatusCode, Json<ApiError>)> {
     let limit = params.limit.unwrap_or(20).min(100);
     let offset = params.offset.unwrap_or(0);
 
-    let benchmarks = if let Some(search) = &params.search {
-        let search_pattern = format!("%{}%", search);
-        sqlx::query_as::<_, Benchmark>(
-            r#"
-            SELECT id, name, dataset_id, task, description, created_at, updated_at
-            FROM benchmarks
-            WHERE name ILIKE $1 OR task ILIKE $1
-            ORDER BY name
-            LIMIT $2 OFFSET $3
-            "#,
-        )
-        .bind(search_pattern)
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&state.pool)
-        .await
-    } else {
-        sqlx::query_as::<_, Benchmark>(
-            r#"
-            SELECT id, name, dataset_id, task, description, created_at, updated_at
-            FROM benchmarks
-            ORDER BY name
-            LIMIT $1 OFFSET $2
-            "#,
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&state.pool)
-        .await
-    };
+    let results = state
+        .repository
+        .benchmark_results(include_deleted, limit, offset)
+        .await;
 
-    benchmarks.map(Json).map_err(|e| {
+    results.map(Json).map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiError {
@@ -651,154 +2030,258 @@ async fn get_benchmarks(
     })
 }
 
-async fn get_benchmark_by_id(
+// ============================================================================
+// Handlers: Badges
+// ============================================================================
+
+/// Shields.io endpoint badge for a paper.
+///
+/// Shows the paper's best leaderboard position ("SOTA: 84.2 mAP on COCO") when
+/// it holds the top result on a benchmark that other papers also report,
+/// ranked in the metric's direction from `metrics`, otherwise its
+/// implementation count.
+async fn get_paper_badge(
     State(state): State<AppState>,
-    Path(id): Path<uuid::Uuid>,
-) -> Result<Json<BenchmarkWithDataset>, (StatusCode, Json<ApiError>)> {
-    let benchmark = sqlx::query_as::<_, Benchmark>(
-        r#"
-        SELECT id, name, dataset_id, task, description, created_at, updated_at
-        FROM benchmarks WHERE id = $1
-        "#,
-    )
-    .bind(id)
-    .fetch_optional(&state.pool)
-    .await
-    .map_err(|e| {
+    Path(arxiv_id): Path<String>,
+) -> Result<Json<ShieldsBadge>, (StatusCode, Json<ApiError>)> {
+    let paper = state
+        .repository
+        .paper_by_arxiv_base_id(arxiv_base_id(&arxiv_id), false)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    let paper = paper.ok_or_else(|| {
         (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::NOT_FOUND,
             Json(ApiError {
-                error: e.to_string(),
+                error: "Paper not found".to_string(),
             }),
         )
     })?;
 
-    let benchmark = benchmark.ok_or_else(|| {
+    let sota = state.repository.leaderboard_top(paper.id).await.map_err(|e| {
         (
-            StatusCode::NOT_FOUND,
+            StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiError {
-                error: "Benchmark not found".to_string(),
+                error: e.to_string(),
             }),
         )
     })?;
 
-    let dataset = if let Some(dataset_id) = benchmark.dataset_id {
-        sqlx::query_as::<_, Dataset>(
-            r#"
-            SELECT id, name, description, modalities, task_categories, languages,
-                   size, homepage_url, github_url, paper_url, created_at, updated_at
-            FROM datasets WHERE id = $1
-            "#,
-        )
-        .bind(dataset_id)
-        .fetch_optional(&state.pool)
+    if let Some(sota) = sota {
+        return Ok(Json(ShieldsBadge {
+            schema_version: 1,
+            label: "SOTA".to_string(),
+            message: format!(
+                "{} {} on {}",
+                sota.metric_value.normalize(),
+                sota.metric_name,
+                sota.dataset_name
+            ),
+            color: "brightgreen".to_string(),
+            cache_seconds: 3600,
+        }));
+    }
+
+    let implementations = state
+        .repository
+        .paper_implementations(paper.id, false)
         .await
-        .ok()
-        .flatten()
-    } else {
-        None
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    let (message, color) = match implementations.len() {
+        0 => ("no code yet".to_string(), "lightgrey"),
+        1 => ("1 implementation".to_string(), "blue"),
+        n => (format!("{} implementations", n), "blue"),
     };
 
-    Ok(Json(BenchmarkWithDataset { benchmark, dataset }))
+    Ok(Json(ShieldsBadge {
+        schema_version: 1,
+        label: "CodeWithPapers".to_string(),
+        message,
+        color: color.to_string(),
+        cache_seconds: 3600,
+    }))
 }
 
 // ============================================================================
-// Handlers: Implementations
+// Handlers: Events
 // ============================================================================
 
-async fn get_implementations(
+/// Server-sent events for newly ingested papers. Answers 501 on databases
+/// that can't announce inserts (SQLite).
+async fn stream_paper_events(
     State(state): State<AppState>,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<Vec<Implementation>>, (StatusCode, Json<ApiError>)> {
-    let limit = params.limit.unwrap_or(20).min(100);
-    let offset = params.offset.unwrap_or(0);
-
-    let implementations = sqlx::query_as::<_, Implementation>(
-        r#"
-        SELECT id, paper_id, github_url, framework, stars, is_official, created_at, updated_at
-        FROM implementations
-        ORDER BY stars DESC NULLS LAST
-        LIMIT $1 OFFSET $2
-        "#,
-    )
-    .bind(limit)
-    .bind(offset)
-    .fetch_all(&state.pool)
-    .await;
-
-    implementations.map(Json).map_err(|e| {
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, Json<ApiError>)> {
+    let events = state.repository.paper_events().await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiError {
                 error: e.to_string(),
             }),
         )
-    })
-}
+    })?;
 
-async fn get_implementation_by_id(
-    State(state): State<AppState>,
-    Path(id): Path<uuid::Uuid>,
-) -> Result<Json<Implementation>, (StatusCode, Json<ApiError>)> {
-    let implementation = sqlx::query_as::<_, Implementation>(
-        r#"
-        SELECT id, paper_id, github_url, framework, stars, is_official, created_at, updated_at
-        FROM implementations WHERE id = $1
-        "#,
-    )
-    .bind(id)
-    .fetch_optional(&state.pool)
-    .await
-    .map_err(|e| {
+    let events = events.ok_or_else(|| {
         (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::NOT_IMPLEMENTED,
             Json(ApiError {
-                error: e.to_string(),
+                error: "The paper event stream needs Postgres".to_string(),
             }),
         )
     })?;
 
-    implementation.map(Json).ok_or_else(|| {
-        (
-            StatusCode::NOT_FOUND,
-            Json(ApiError {
-                error: "Implementation not found".to_string(),
-            }),
-        )
-    })
+    // Payloads are already JSON-encoded PaperEvents
+    let events = events.map(|payload| Ok(Event::default().event("paper").data(payload)));
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
 }
 
 // ============================================================================
-// Handlers: Benchmark Results
+// Handlers: Webhooks
 // ============================================================================
 
-async fn get_benchmark_results(
+/// Check a GitHub `X-Hub-Signature-256` header (`sha256=<hex HMAC of body>`).
+pub fn verify_github_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    use hmac::{Hmac, Mac};
+
+    let Some(signature) = signature
+        .strip_prefix("sha256=")
+        .and_then(|hex_digest| hex::decode(hex_digest).ok())
+    else {
+        return false;
+    };
+
+    let Ok(mut mac) = Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    // Constant-time comparison
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// Receive GitHub `star`, `push` and `repository` events and refresh stars,
+/// last push time and archived status for implementations of that repo.
+async fn github_webhook(
     State(state): State<AppState>,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<Vec<BenchmarkResult>>, (StatusCode, Json<ApiError>)> {
-    let limit = params.limit.unwrap_or(20).min(100);
-    let offset = params.offset.unwrap_or(0);
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<WebhookResponse>, (StatusCode, Json<ApiError>)> {
+    let secret = state.config.github_webhook_secret.as_deref().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiError {
+                error: "GitHub webhooks are not configured".to_string(),
+            }),
+        )
+    })?;
 
-    let results = sqlx::query_as::<_, BenchmarkResult>(
-        r#"
-        SELECT id, paper_id, benchmark_id, implementation_id, metric_name,
-               metric_value, extra_data, created_at
-        FROM benchmark_results
-        ORDER BY metric_value DESC
-        LIMIT $1 OFFSET $2
-        "#,
-    )
-    .bind(limit)
-    .bind(offset)
-    .fetch_all(&state.pool)
-    .await;
+    let signature = headers
+        .get("X-Hub-Signature-256")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if !verify_github_signature(secret, &body, signature) {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ApiError {
+                error: "Invalid webhook signature".to_string(),
+            }),
+        ));
+    }
 
-    results.map(Json).map_err(|e| {
+    let event = headers
+        .get("X-GitHub-Event")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+
+    let payload: serde_json::Value = serde_json::from_slice(&body).map_err(|e| {
         (
-            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::BAD_REQUEST,
             Json(ApiError {
-                error: e.to_string(),
+                error: format!("Invalid webhook payload: {}", e),
             }),
         )
-    })
+    })?;
+    let repository = &payload["repository"];
+    let full_name = repository["full_name"].as_str().map(str::to_string);
+
+    let Some(repo) = full_name
+        .as_deref()
+        .filter(|_| matches!(event.as_str(), "star" | "push" | "repository"))
+    else {
+        // Pings and other events are acknowledged but not acted on
+        return Ok(Json(WebhookResponse {
+            event,
+            repository: full_name,
+            implementations_updated: 0,
+        }));
+    };
+
+    // Push events send pushed_at as a Unix timestamp, others as an ISO string
+    let pushed_at = match &repository["pushed_at"] {
+        serde_json::Value::Number(n) => n
+            .as_i64()
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0)),
+        serde_json::Value::String(s) => chrono::DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|dt| dt.to_utc()),
+        _ => None,
+    };
+
+    let update = GithubRepoUpdate {
+        full_name: repo.to_string(),
+        stars: repository["stargazers_count"].as_i64().map(|stars| stars as i32),
+        pushed_at,
+        archived: repository["archived"].as_bool(),
+        disabled: repository["disabled"].as_bool(),
+    };
+    let implementations_updated = state
+        .repository
+        .update_github_repo(&update)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error: e.to_string(),
+                }),
+            )
+        })?;
+
+    Ok(Json(WebhookResponse {
+        event,
+        repository: full_name,
+        implementations_updated,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orcid_checksums_are_validated() {
+        assert_eq!(
+            normalize_orcid("https://orcid.org/0000-0002-1825-0097").as_deref(),
+            Some("0000-0002-1825-0097")
+        );
+        assert_eq!(normalize_orcid("000000021694233x").as_deref(), Some("0000-0002-1694-233X"));
+        assert_eq!(normalize_orcid("0000-0002-1825-0098"), None);
+        assert_eq!(normalize_orcid("0000-0002-1825"), None);
+    }
 }
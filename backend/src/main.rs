@@ -1,14 +1,74 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use sqlx::postgres::PgPoolOptions;
 use std::env;
 use dotenvy::dotenv;
-use backend::{create_app, search::SearchIndex};
+use backend::search::{IndexConfig, SearchIndex};
+use backend::postgres::PgRepository;
+use backend::repository::Repository;
+use backend::{create_app_with_repository, sqlite, storage::PaperStore, AppConfig, ReadReplica};
 
 #[tokio::main]
 async fn main() {
     dotenv().ok();
 
+    // SQLite mode for local development; see DATABASE_SETUP.md
+    let sqlite_url = env::var("DATABASE_URL").ok().filter(|url| sqlite::is_sqlite_url(url));
+    let (repository, search_index): (Arc<dyn Repository>, _) = match sqlite_url {
+        Some(sqlite_url) => {
+            let pool = sqlite::connect(&sqlite_url)
+                .await
+                .expect("Failed to open the SQLite database");
+            println!("SQLite mode: serving {}", sqlite_url);
+            println!("Run `cargo run --bin seed` to load the sample papers.");
+            println!("Search uses LIKE, and the paper event stream needs Postgres.");
+            (Arc::new(sqlite::SqliteRepository::new(pool)), None)
+        }
+        None => (connect_postgres().await, open_search_index()),
+    };
+
+    // Paper store caches PDFs on disk (optional)
+    let store_path = env::var("PAPER_STORE_PATH")
+        .unwrap_or_else(|_| "./data/paper_store".to_string());
+
+    let paper_store = match PaperStore::open(&store_path) {
+        Ok(store) => {
+            println!("Paper store at {}", store_path);
+            Some(Arc::new(store))
+        }
+        Err(e) => {
+            println!(
+                "Paper store not available at {} ({}). PDF requests will redirect upstream.",
+                store_path, e
+            );
+            None
+        }
+    };
+
+    // Secrets for optional features; each is disabled while unset
+    let secret = |name: &str| {
+        let value = env::var(name).ok().filter(|value| !value.is_empty());
+        if value.is_none() {
+            println!("{} not set. The features it enables are disabled.", name);
+        }
+        value
+    };
+    let config = AppConfig {
+        admin_token: secret("ADMIN_TOKEN"),
+        github_webhook_secret: secret("GITHUB_WEBHOOK_SECRET"),
+    };
+
+    let app = create_app_with_repository(repository, search_index, paper_store, config);
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], 8000));
+    println!("Listening on {}", addr);
+    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
+    axum::serve(listener, app).await.unwrap();
+}
+
+/// Postgres repository from POSTGRES_URI, with the optional read replica.
+async fn connect_postgres() -> Arc<dyn Repository> {
     let database_url = env::var("POSTGRES_URI")
         .expect("POSTGRES_URI must be set");
 
@@ -18,14 +78,32 @@ async fn main() {
         .await
         .expect("Failed to create pool");
 
-    // Try to load Tantivy search index (optional)
+    // Optional read replica for GET handlers. Connected lazily, so an
+    // unreachable replica just leaves reads on the primary.
+    let read_replica = match env::var("POSTGRES_READ_URI").ok().filter(|uri| !uri.is_empty()) {
+        Some(read_url) => {
+            let replica_pool = PgPoolOptions::new()
+                .max_connections(10)
+                .connect_lazy(&read_url)
+                .expect("Invalid POSTGRES_READ_URI");
+            println!("Serving reads from the replica at POSTGRES_READ_URI while it is healthy");
+            Some(ReadReplica::new(replica_pool, Duration::from_secs(10)))
+        }
+        None => None,
+    };
+
+    Arc::new(PgRepository::new(pool, read_replica))
+}
+
+/// The Tantivy search index, if one has been built.
+fn open_search_index() -> Option<Arc<SearchIndex>> {
     let index_path = env::var("TANTIVY_INDEX_PATH")
         .unwrap_or_else(|_| "./data/tantivy_index".to_string());
 
-    let search_index = match SearchIndex::open(&index_path) {
+    match SearchIndex::open(&index_path) {
         Ok(index) => {
             println!("Tantivy search index loaded from {}", index_path);
-            Some(Arc::new(index))
+            Some(Arc::new(index.with_config(IndexConfig::from_env())))
         }
         Err(e) => {
             println!(
@@ -35,13 +113,5 @@ async fn main() {
             println!("Run `cargo run --bin build_search_index` to build the index.");
             None
         }
-    };
-
-    let app = create_app(pool, search_index);
-
-    // Run our application
-    let addr = SocketAddr::from(([127, 0, 0, 1], 8000));
-    println!("Listening on {}", addr);
-    let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    }
 }
@@ -0,0 +1,565 @@
+//! [`Repository`] on Postgres, the production database.
+//!
+//! Reads go to the read replica while it is healthy; writes, and reads that
+//! must see them, go to the primary.
+
+use crate::repository::*;
+use crate::{
+    latest_arxiv_version_filter, ArxivVersion, Benchmark, BenchmarkResult, Dataset, DatasetFile,
+    Dependency, Implementation, LanguageBreakdown, Package, Paper, PaperAuthor, PaperSummary,
+    PaperTranslation, RankedImplementation, ReadReplica, TaskNode, TaskSummary,
+    ARXIV_LATEST_FIRST, PAPER_EVENTS_CHANNEL,
+};
+use axum::async_trait;
+use futures::stream::{BoxStream, StreamExt};
+use sqlx::{postgres::PgListener, Pool, Postgres};
+
+/// When a paper was published, for "latest papers" ordering: `published_at`,
+/// else midnight UTC on `published_date`. Matches `idx_papers_publication_time`.
+const PUBLICATION_TIME: &str =
+    "COALESCE(published_at, published_date::timestamp AT TIME ZONE 'UTC')";
+
+#[derive(Clone, Debug)]
+pub struct PgRepository {
+    pool: Pool<Postgres>,
+    read_replica: Option<ReadReplica>,
+}
+
+impl PgRepository {
+    pub fn new(pool: Pool<Postgres>, read_replica: Option<ReadReplica>) -> Self {
+        Self { pool, read_replica }
+    }
+
+    /// Pool for read-only queries: the read replica while it is healthy,
+    /// otherwise the primary.
+    fn read_pool(&self) -> &Pool<Postgres> {
+        match &self.read_replica {
+            Some(replica) if replica.is_healthy() => &replica.pool,
+            _ => &self.pool,
+        }
+    }
+}
+
+#[async_trait]
+impl Repository for PgRepository {
+    fn primary(&self) -> Box<dyn Repository> {
+        Box::new(Self::new(self.pool.clone(), None))
+    }
+
+    async fn catalog_counts(&self) -> sqlx::Result<CatalogCounts> {
+        sqlx::query_as(CATALOG_COUNTS)
+            .fetch_one(self.read_pool())
+            .await
+    }
+
+    async fn last_ingested_at(
+        &self,
+    ) -> sqlx::Result<Vec<(String, chrono::DateTime<chrono::Utc>)>> {
+        sqlx::query_as(LAST_INGESTED_AT)
+            .fetch_all(self.read_pool())
+            .await
+    }
+
+    async fn papers(&self, query: &PaperQuery) -> sqlx::Result<Vec<Paper>> {
+        let order = if query.ascending { "ASC" } else { "DESC" };
+
+        sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM papers
+            WHERE ($1::text IS NULL
+                   OR title ILIKE $1
+                   OR COALESCE(abstract_plain, abstract) ILIKE $1)
+              AND ($4 OR deleted_at IS NULL)
+              AND ($5::boolean IS NULL OR has_code = $5)
+              AND {}
+            ORDER BY {} {order} NULLS LAST, id {order}
+            LIMIT $2 OFFSET $3
+            "#,
+            PAPER_COLUMNS,
+            latest_arxiv_version_filter(),
+            PUBLICATION_TIME,
+        ))
+        .bind(query.search.as_ref().map(|search| format!("%{}%", search)))
+        .bind(query.limit)
+        .bind(query.offset)
+        .bind(query.include_deleted)
+        .bind(query.has_code)
+        .fetch_all(self.read_pool())
+        .await
+    }
+
+    async fn papers_by_ids(
+        &self,
+        ids: &[uuid::Uuid],
+        include_deleted: bool,
+    ) -> sqlx::Result<Vec<Paper>> {
+        sqlx::query_as(&format!(
+            "SELECT {} FROM papers WHERE id = ANY($1) AND ($2 OR deleted_at IS NULL)",
+            PAPER_COLUMNS
+        ))
+        .bind(ids)
+        .bind(include_deleted)
+        .fetch_all(self.read_pool())
+        .await
+    }
+
+    async fn latest_version_ids(
+        &self,
+        ids: &[uuid::Uuid],
+    ) -> sqlx::Result<Vec<(uuid::Uuid, uuid::Uuid)>> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT hit.id, COALESCE(latest.id, hit.id)
+            FROM papers hit
+            LEFT JOIN LATERAL (
+                SELECT id FROM papers
+                WHERE arxiv_base_id = hit.arxiv_base_id
+                ORDER BY {}
+                LIMIT 1
+            ) latest ON TRUE
+            WHERE hit.id = ANY($1)
+            "#,
+            ARXIV_LATEST_FIRST
+        ))
+        .bind(ids)
+        .fetch_all(self.read_pool())
+        .await
+    }
+
+    async fn arxiv_versions(
+        &self,
+        base_ids: &[String],
+        include_deleted: bool,
+    ) -> sqlx::Result<Vec<ArxivVersion>> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT arxiv_base_id, id, arxiv_id, arxiv_version AS version, published_date
+            FROM papers
+            WHERE arxiv_base_id = ANY($1) AND ($2 OR deleted_at IS NULL)
+            ORDER BY {}
+            "#,
+            ARXIV_LATEST_FIRST
+        ))
+        .bind(base_ids)
+        .bind(include_deleted)
+        .fetch_all(self.read_pool())
+        .await
+    }
+
+    async fn translations(
+        &self,
+        paper_ids: &[uuid::Uuid],
+        language: &str,
+    ) -> sqlx::Result<Vec<PaperTranslation>> {
+        sqlx::query_as(
+            r#"
+            SELECT paper_id, language, title, abstract, provider, updated_at
+            FROM paper_translations
+            WHERE paper_id = ANY($1) AND language = lower($2)
+            "#,
+        )
+        .bind(paper_ids)
+        .bind(language)
+        .fetch_all(self.read_pool())
+        .await
+    }
+
+    async fn paper_by_id(
+        &self,
+        id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Paper>> {
+        sqlx::query_as(&paper_where("id = $1"))
+            .bind(id)
+            .bind(include_deleted)
+            .fetch_optional(self.read_pool())
+            .await
+    }
+
+    async fn paper_by_doi(&self, doi: &str, include_deleted: bool) -> sqlx::Result<Option<Paper>> {
+        sqlx::query_as(&paper_where("doi = $1"))
+            .bind(doi)
+            .bind(include_deleted)
+            .fetch_optional(self.read_pool())
+            .await
+    }
+
+    async fn paper_by_arxiv_base_id(
+        &self,
+        base_id: &str,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Paper>> {
+        sqlx::query_as(&paper_by_arxiv_base_id())
+            .bind(base_id)
+            .bind(include_deleted)
+            .fetch_optional(self.read_pool())
+            .await
+    }
+
+    async fn paper_implementations(
+        &self,
+        paper_id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Vec<RankedImplementation>> {
+        sqlx::query_as(&ranked_paper_implementations())
+            .bind(paper_id)
+            .bind(include_deleted)
+            .fetch_all(self.read_pool())
+            .await
+    }
+
+    async fn paper_languages(
+        &self,
+        paper_id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Vec<LanguageBreakdown>> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT lang.key AS language,
+                   SUM(lang.value::bigint)::bigint AS bytes,
+                   (100.0 * SUM(lang.value::bigint) / SUM(SUM(lang.value::bigint)) OVER ())::float8
+                       AS percentage
+            FROM {}, jsonb_each_text(implementations.languages) AS lang
+            GROUP BY lang.key
+            ORDER BY bytes DESC
+            "#,
+            paper_implementations()
+        ))
+        .bind(paper_id)
+        .bind(include_deleted)
+        .fetch_all(self.read_pool())
+        .await
+    }
+
+    async fn paper_author_orcids(&self, paper_id: uuid::Uuid) -> sqlx::Result<Vec<PaperAuthor>> {
+        sqlx::query_as(&paper_author_orcids())
+            .bind(paper_id)
+            .fetch_all(self.read_pool())
+            .await
+    }
+
+    async fn author_name(
+        &self,
+        orcid: &str,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<String>> {
+        sqlx::query_scalar(AUTHOR_NAME)
+            .bind(orcid)
+            .bind(include_deleted)
+            .fetch_optional(self.read_pool())
+            .await
+    }
+
+    async fn author_papers(
+        &self,
+        orcid: &str,
+        include_deleted: bool,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<PaperSummary>> {
+        sqlx::query_as(&author_papers())
+            .bind(orcid)
+            .bind(include_deleted)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(self.read_pool())
+            .await
+    }
+
+    async fn datasets(
+        &self,
+        search: Option<&str>,
+        include_deleted: bool,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<Dataset>> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM datasets
+            WHERE ($1::text IS NULL OR name ILIKE $1 OR description ILIKE $1)
+              AND ($4 OR deleted_at IS NULL)
+            ORDER BY name
+            LIMIT $2 OFFSET $3
+            "#,
+            DATASET_COLUMNS
+        ))
+        .bind(search.map(|search| format!("%{}%", search)))
+        .bind(limit)
+        .bind(offset)
+        .bind(include_deleted)
+        .fetch_all(self.read_pool())
+        .await
+    }
+
+    async fn dataset_by_id(
+        &self,
+        id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Dataset>> {
+        sqlx::query_as(&dataset_by_id())
+            .bind(id)
+            .bind(include_deleted)
+            .fetch_optional(self.read_pool())
+            .await
+    }
+
+    async fn dataset_files(&self, dataset_id: uuid::Uuid) -> sqlx::Result<Vec<DatasetFile>> {
+        sqlx::query_as(DATASET_FILES)
+            .bind(dataset_id)
+            .fetch_all(self.read_pool())
+            .await
+    }
+
+    async fn benchmarks(
+        &self,
+        search: Option<&str>,
+        include_deleted: bool,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<Benchmark>> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM benchmarks
+            WHERE ($1::text IS NULL OR name ILIKE $1 OR task ILIKE $1)
+              AND ($4 OR deleted_at IS NULL)
+            ORDER BY name
+            LIMIT $2 OFFSET $3
+            "#,
+            BENCHMARK_COLUMNS
+        ))
+        .bind(search.map(|search| format!("%{}%", search)))
+        .bind(limit)
+        .bind(offset)
+        .bind(include_deleted)
+        .fetch_all(self.read_pool())
+        .await
+    }
+
+    async fn benchmark_by_id(
+        &self,
+        id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Benchmark>> {
+        sqlx::query_as(&benchmark_by_id())
+            .bind(id)
+            .bind(include_deleted)
+            .fetch_optional(self.read_pool())
+            .await
+    }
+
+    async fn benchmark_results(
+        &self,
+        include_deleted: bool,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<BenchmarkResult>> {
+        sqlx::query_as(
+            r#"
+            SELECT id, paper_id, benchmark_id, implementation_id, metric_name,
+                   metric_value, extra_data, created_at, updated_at, deleted_at
+            FROM benchmark_results
+            WHERE ($3 OR deleted_at IS NULL)
+            ORDER BY metric_value DESC
+            LIMIT $1 OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .bind(include_deleted)
+        .fetch_all(self.read_pool())
+        .await
+    }
+
+    async fn leaderboard_top(&self, paper_id: uuid::Uuid) -> sqlx::Result<Option<LeaderboardTop>> {
+        let top: Option<(String, rust_decimal::Decimal, String)> = sqlx::query_as(
+            r#"
+            SELECT br.metric_name, br.metric_value, COALESCE(d.name, b.name)
+            FROM benchmark_results br
+            JOIN benchmarks b ON b.id = br.benchmark_id
+            LEFT JOIN datasets d ON d.id = b.dataset_id
+            LEFT JOIN metrics m ON lower(m.name) = lower(br.metric_name)
+            WHERE br.paper_id = $1
+              AND br.deleted_at IS NULL
+              AND b.deleted_at IS NULL
+              AND EXISTS (
+                  SELECT 1 FROM benchmark_results other
+                  WHERE other.benchmark_id = br.benchmark_id
+                    AND other.metric_name = br.metric_name
+                    AND other.paper_id <> br.paper_id
+                    AND other.deleted_at IS NULL
+              )
+              AND NOT EXISTS (
+                  SELECT 1 FROM benchmark_results other
+                  WHERE other.benchmark_id = br.benchmark_id
+                    AND other.metric_name = br.metric_name
+                    AND other.deleted_at IS NULL
+                    AND CASE WHEN COALESCE(m.higher_is_better, TRUE)
+                             THEN other.metric_value > br.metric_value
+                             ELSE other.metric_value < br.metric_value
+                        END
+              )
+            ORDER BY (
+                SELECT COUNT(*) FROM benchmark_results c
+                WHERE c.benchmark_id = br.benchmark_id
+                  AND c.metric_name = br.metric_name
+                  AND c.deleted_at IS NULL
+            ) DESC, b.name
+            LIMIT 1
+            "#,
+        )
+        .bind(paper_id)
+        .fetch_optional(self.read_pool())
+        .await?;
+
+        Ok(top.map(|(metric_name, metric_value, dataset_name)| LeaderboardTop {
+            metric_name,
+            metric_value,
+            dataset_name,
+        }))
+    }
+
+    async fn task_nodes(&self, root: Option<uuid::Uuid>) -> sqlx::Result<Vec<TaskNode>> {
+        sqlx::query_as(TASK_NODES)
+            .bind(root)
+            .fetch_all(self.read_pool())
+            .await
+    }
+
+    async fn task_ancestors(&self, id: uuid::Uuid) -> sqlx::Result<Vec<TaskSummary>> {
+        sqlx::query_as(TASK_ANCESTORS)
+            .bind(id)
+            .fetch_all(self.read_pool())
+            .await
+    }
+
+    async fn set_task_parent(
+        &self,
+        id: uuid::Uuid,
+        parent: Option<&str>,
+    ) -> sqlx::Result<TaskParentChange> {
+        let mut tx = self.pool.begin().await?;
+
+        let parent_id: Option<uuid::Uuid> = match parent {
+            Some(name) => Some(
+                sqlx::query_scalar(UPSERT_TASK)
+                    .bind(name)
+                    .fetch_one(&mut *tx)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        // check_task_parent rejects cycles with a check violation
+        let result = match sqlx::query("UPDATE tasks SET parent_id = $2 WHERE id = $1")
+            .bind(id)
+            .bind(parent_id)
+            .execute(&mut *tx)
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => match e.as_database_error() {
+                Some(db_error) if db_error.is_check_violation() => {
+                    return Ok(TaskParentChange::Cycle(db_error.message().to_string()));
+                }
+                _ => return Err(e),
+            },
+        };
+
+        if result.rows_affected() == 0 {
+            return Ok(TaskParentChange::NotFound);
+        }
+        tx.commit().await?;
+
+        Ok(TaskParentChange::Updated)
+    }
+
+    async fn implementations(
+        &self,
+        query: &ImplementationQuery,
+    ) -> sqlx::Result<Vec<Implementation>> {
+        sqlx::query_as(&implementations())
+            .bind(query.limit)
+            .bind(query.offset)
+            .bind(query.reproducible)
+            .bind(query.archived)
+            .bind(query.include_deleted)
+            .fetch_all(self.read_pool())
+            .await
+    }
+
+    async fn implementation_by_id(
+        &self,
+        id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Implementation>> {
+        sqlx::query_as(&implementation_by_id())
+            .bind(id)
+            .bind(include_deleted)
+            .fetch_optional(self.read_pool())
+            .await
+    }
+
+    async fn implementation_packages(
+        &self,
+        implementation_id: uuid::Uuid,
+    ) -> sqlx::Result<Vec<Package>> {
+        sqlx::query_as(IMPLEMENTATION_PACKAGES)
+            .bind(implementation_id)
+            .fetch_all(self.read_pool())
+            .await
+    }
+
+    async fn implementation_dependencies(
+        &self,
+        implementation_id: uuid::Uuid,
+    ) -> sqlx::Result<Vec<Dependency>> {
+        sqlx::query_as(IMPLEMENTATION_DEPENDENCIES)
+            .bind(implementation_id)
+            .fetch_all(self.read_pool())
+            .await
+    }
+
+    async fn papers_by_dependency(
+        &self,
+        query: &DependencyQuery,
+    ) -> sqlx::Result<Vec<PaperSummary>> {
+        sqlx::query_as(&papers_by_dependency())
+            .bind(&query.name)
+            .bind(query.limit)
+            .bind(query.offset)
+            .bind(query.official)
+            .bind(query.include_deleted)
+            .fetch_all(self.read_pool())
+            .await
+    }
+
+    async fn update_github_repo(&self, update: &GithubRepoUpdate) -> sqlx::Result<u64> {
+        let result = sqlx::query(UPDATE_GITHUB_REPO)
+            .bind(&update.full_name)
+            .bind(update.stars)
+            .bind(update.pushed_at)
+            .bind(update.archived)
+            .bind(update.disabled)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Each call opens its own `LISTEN` connection, so events are relayed as
+    /// soon as they are committed.
+    async fn paper_events(&self) -> sqlx::Result<Option<BoxStream<'static, String>>> {
+        // NOTIFY isn't replicated, so always listen on the primary
+        let mut listener = PgListener::connect_with(&self.pool).await?;
+        listener.listen(PAPER_EVENTS_CHANNEL).await?;
+
+        let events = listener.into_stream().filter_map(|notification| async move {
+            Some(notification.ok()?.payload().to_string())
+        });
+
+        Ok(Some(events.boxed()))
+    }
+}
@@ -0,0 +1,591 @@
+//! Data access for the API handlers.
+//!
+//! The handlers in `lib.rs` read and write through [`Repository`], which has
+//! one implementation per database: [`crate::postgres::PgRepository`] for
+//! production and [`crate::sqlite::SqliteRepository`] for local development.
+//! Request parsing, status codes and response shaping live in the handlers;
+//! a repository method only runs the query in its database's SQL dialect.
+
+use crate::{
+    ArxivVersion, Benchmark, BenchmarkResult, Dataset, DatasetFile, Dependency, Implementation,
+    LanguageBreakdown, Package, Paper, PaperAuthor, PaperSummary, PaperTranslation,
+    RankedImplementation, TaskNode, TaskSummary,
+};
+use axum::async_trait;
+use futures::stream::BoxStream;
+
+/// Filters for listing papers, newest first unless `ascending`. Superseded
+/// arXiv versions are always left out.
+#[derive(Debug, Default)]
+pub struct PaperQuery {
+    /// Substring to find in the title or abstract, case-insensitively
+    pub search: Option<String>,
+    pub has_code: Option<bool>,
+    pub include_deleted: bool,
+    pub ascending: bool,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Filters for listing implementations.
+#[derive(Debug, Default)]
+pub struct ImplementationQuery {
+    /// Repos with a Dockerfile, conda env, or pinned requirements
+    pub reproducible: Option<bool>,
+    pub archived: Option<bool>,
+    pub include_deleted: bool,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Filters for finding papers by code dependency.
+#[derive(Debug, Default)]
+pub struct DependencyQuery {
+    /// PEP 503 normalized package name
+    pub name: String,
+    /// Only consider official implementations
+    pub official: Option<bool>,
+    pub include_deleted: bool,
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Live row counts for `/api/stats`.
+#[derive(Debug, Default, sqlx::FromRow)]
+pub struct CatalogCounts {
+    pub papers: i64,
+    pub datasets: i64,
+    pub benchmarks: i64,
+    pub implementations: i64,
+    pub results: i64,
+    pub papers_with_code: i64,
+}
+
+/// A paper's top result on a leaderboard, for its badge.
+#[derive(Debug)]
+pub struct LeaderboardTop {
+    pub metric_name: String,
+    pub metric_value: rust_decimal::Decimal,
+    /// Dataset name, or the benchmark's name when it has no dataset
+    pub dataset_name: String,
+}
+
+/// Outcome of moving a task in the taxonomy.
+#[derive(Debug)]
+pub enum TaskParentChange {
+    Updated,
+    NotFound,
+    /// The new parent is the task itself or one of its subtasks
+    Cycle(String),
+}
+
+/// Repository metadata from a GitHub webhook; `None` fields are left as they are.
+#[derive(Debug, Default)]
+pub struct GithubRepoUpdate {
+    /// `owner/repo`, matched case-insensitively
+    pub full_name: String,
+    pub stars: Option<i32>,
+    pub pushed_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub archived: Option<bool>,
+    pub disabled: Option<bool>,
+}
+
+/// Every query the API runs. Methods taking `include_deleted` also return
+/// soft-deleted rows when it is set; the others only see live rows unless
+/// documented otherwise.
+#[async_trait]
+pub trait Repository: Send + Sync {
+    /// The same database, reading from the primary only, for reading back
+    /// a write that a replica may not have yet.
+    fn primary(&self) -> Box<dyn Repository>;
+
+    // Stats
+
+    async fn catalog_counts(&self) -> sqlx::Result<CatalogCounts>;
+
+    /// When each ingestion source last finished a run.
+    async fn last_ingested_at(
+        &self,
+    ) -> sqlx::Result<Vec<(String, chrono::DateTime<chrono::Utc>)>>;
+
+    // Papers
+
+    async fn papers(&self, query: &PaperQuery) -> sqlx::Result<Vec<Paper>>;
+
+    /// Papers with the given IDs, in no particular order.
+    async fn papers_by_ids(
+        &self,
+        ids: &[uuid::Uuid],
+        include_deleted: bool,
+    ) -> sqlx::Result<Vec<Paper>>;
+
+    /// `(id, latest_id)` for each given paper: the ID of the latest arXiv
+    /// version of the same paper, or the paper's own ID.
+    async fn latest_version_ids(
+        &self,
+        ids: &[uuid::Uuid],
+    ) -> sqlx::Result<Vec<(uuid::Uuid, uuid::Uuid)>>;
+
+    /// All versions of the given arXiv base IDs, latest first.
+    async fn arxiv_versions(
+        &self,
+        base_ids: &[String],
+        include_deleted: bool,
+    ) -> sqlx::Result<Vec<ArxivVersion>>;
+
+    /// Translations of the given papers into `language`.
+    async fn translations(
+        &self,
+        paper_ids: &[uuid::Uuid],
+        language: &str,
+    ) -> sqlx::Result<Vec<PaperTranslation>>;
+
+    async fn paper_by_id(&self, id: uuid::Uuid, include_deleted: bool)
+        -> sqlx::Result<Option<Paper>>;
+
+    /// The paper with a normalized DOI.
+    async fn paper_by_doi(&self, doi: &str, include_deleted: bool) -> sqlx::Result<Option<Paper>>;
+
+    /// The latest version of the paper with an arXiv base ID.
+    async fn paper_by_arxiv_base_id(
+        &self,
+        base_id: &str,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Paper>>;
+
+    /// A paper's implementations across its arXiv versions, one per
+    /// repository, ranked by `IMPLEMENTATION_RANKING`.
+    async fn paper_implementations(
+        &self,
+        paper_id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Vec<RankedImplementation>>;
+
+    /// Languages of those implementations, most code first.
+    async fn paper_languages(
+        &self,
+        paper_id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Vec<LanguageBreakdown>>;
+
+    /// Authors with an ORCID iD found on any arXiv version of the paper.
+    async fn paper_author_orcids(&self, paper_id: uuid::Uuid) -> sqlx::Result<Vec<PaperAuthor>>;
+
+    // Authors
+
+    /// The author's name on their most recent paper, if any paper has the ORCID.
+    async fn author_name(&self, orcid: &str, include_deleted: bool)
+        -> sqlx::Result<Option<String>>;
+
+    /// Papers with the ORCID on any arXiv version, each once as its latest
+    /// version, newest first.
+    async fn author_papers(
+        &self,
+        orcid: &str,
+        include_deleted: bool,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<PaperSummary>>;
+
+    // Datasets
+
+    /// Datasets by name, optionally matching `search` in the name or description.
+    async fn datasets(
+        &self,
+        search: Option<&str>,
+        include_deleted: bool,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<Dataset>>;
+
+    async fn dataset_by_id(
+        &self,
+        id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Dataset>>;
+
+    /// A dataset's files by URL.
+    async fn dataset_files(&self, dataset_id: uuid::Uuid) -> sqlx::Result<Vec<DatasetFile>>;
+
+    // Benchmarks
+
+    /// Benchmarks by name, optionally matching `search` in the name or task.
+    async fn benchmarks(
+        &self,
+        search: Option<&str>,
+        include_deleted: bool,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<Benchmark>>;
+
+    async fn benchmark_by_id(
+        &self,
+        id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Benchmark>>;
+
+    /// Results, highest metric value first.
+    async fn benchmark_results(
+        &self,
+        include_deleted: bool,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<BenchmarkResult>>;
+
+    /// The paper's result on the most contested leaderboard where it ranks
+    /// first, in the metric's direction from `metrics`; unlisted metrics
+    /// count as higher-is-better.
+    async fn leaderboard_top(&self, paper_id: uuid::Uuid) -> sqlx::Result<Option<LeaderboardTop>>;
+
+    // Tasks
+
+    /// Tasks with counts rolled up over their subtasks, by name, limited to
+    /// the subtree under `root` if given. Children are not attached.
+    async fn task_nodes(&self, root: Option<uuid::Uuid>) -> sqlx::Result<Vec<TaskNode>>;
+
+    /// Path from the root task down to the task's parent.
+    async fn task_ancestors(&self, id: uuid::Uuid) -> sqlx::Result<Vec<TaskSummary>>;
+
+    /// Move a task under the task named `parent` (created if missing), or to
+    /// the top level.
+    async fn set_task_parent(
+        &self,
+        id: uuid::Uuid,
+        parent: Option<&str>,
+    ) -> sqlx::Result<TaskParentChange>;
+
+    // Implementations
+
+    /// Implementations, maintained and most starred first.
+    async fn implementations(
+        &self,
+        query: &ImplementationQuery,
+    ) -> sqlx::Result<Vec<Implementation>>;
+
+    async fn implementation_by_id(
+        &self,
+        id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Implementation>>;
+
+    /// Registry packages published from an implementation.
+    async fn implementation_packages(
+        &self,
+        implementation_id: uuid::Uuid,
+    ) -> sqlx::Result<Vec<Package>>;
+
+    async fn implementation_dependencies(
+        &self,
+        implementation_id: uuid::Uuid,
+    ) -> sqlx::Result<Vec<Dependency>>;
+
+    /// Papers whose code (on any arXiv version) declares the dependency,
+    /// each once as its latest version, newest first.
+    async fn papers_by_dependency(&self, query: &DependencyQuery)
+        -> sqlx::Result<Vec<PaperSummary>>;
+
+    /// Apply a GitHub webhook to the implementations of a repository,
+    /// returning how many were updated.
+    async fn update_github_repo(&self, update: &GithubRepoUpdate) -> sqlx::Result<u64>;
+
+    // Events
+
+    /// JSON [`crate::PaperEvent`]s for papers inserted from now on, or `None`
+    /// if the database can't announce them.
+    async fn paper_events(&self) -> sqlx::Result<Option<BoxStream<'static, String>>>;
+}
+
+// ============================================================================
+// Portable SQL
+// ============================================================================
+//
+// Queries that mean the same on Postgres and SQLite, shared so the
+// implementations can't drift. Parameters are bound with their Rust types,
+// so `$1 IS NULL` needs no cast on Postgres.
+
+pub(crate) const PAPER_COLUMNS: &str = "id, title, abstract, abstract_plain, arxiv_id, arxiv_url, \
+     pdf_url, doi, published_date, published_at, authors, has_code, created_at, updated_at, \
+     deleted_at";
+
+pub(crate) const IMPLEMENTATION_COLUMNS: &str = "id, paper_id, github_url, framework, stars, \
+     is_official, has_dockerfile, has_conda_env, has_pinned_requirements, languages, \
+     archived, disabled, pushed_at, created_at, updated_at, deleted_at";
+
+pub(crate) const DATASET_COLUMNS: &str = "id, name, description, modalities, task_categories, \
+     languages, size, homepage_url, github_url, paper_url, created_at, updated_at, deleted_at";
+
+pub(crate) const BENCHMARK_COLUMNS: &str =
+    "id, name, dataset_id, task, description, created_at, updated_at, deleted_at";
+
+/// Live row counts.
+pub(crate) const CATALOG_COUNTS: &str = r#"
+    SELECT (SELECT COUNT(*) FROM papers WHERE deleted_at IS NULL) AS papers,
+           (SELECT COUNT(*) FROM datasets WHERE deleted_at IS NULL) AS datasets,
+           (SELECT COUNT(*) FROM benchmarks WHERE deleted_at IS NULL) AS benchmarks,
+           (SELECT COUNT(*) FROM implementations WHERE deleted_at IS NULL) AS implementations,
+           (SELECT COUNT(*) FROM benchmark_results WHERE deleted_at IS NULL) AS results,
+           (SELECT COUNT(*) FROM papers WHERE has_code AND deleted_at IS NULL) AS papers_with_code
+"#;
+
+pub(crate) const LAST_INGESTED_AT: &str =
+    "SELECT source, MAX(finished_at) FROM ingestion_runs GROUP BY source";
+
+/// Paper `$1`; `$2` includes soft-deleted rows. The same shape serves the
+/// other single-paper lookups.
+pub(crate) fn paper_where(condition: &str) -> String {
+    format!(
+        "SELECT {} FROM papers WHERE {} AND ($2 OR deleted_at IS NULL)",
+        PAPER_COLUMNS, condition
+    )
+}
+
+/// Latest live version of arXiv base ID `$1`; `$2` includes soft-deleted rows.
+pub(crate) fn paper_by_arxiv_base_id() -> String {
+    format!(
+        "{} ORDER BY {} LIMIT 1",
+        paper_where("arxiv_base_id = $1"),
+        crate::ARXIV_LATEST_FIRST
+    )
+}
+
+/// Implementations of paper `$1` across its arXiv versions as a derived
+/// `implementations` table, one row per repository (preferring the one linked
+/// to `$1`). `$2` includes soft-deleted implementations and versions.
+pub(crate) fn paper_implementations() -> String {
+    format!(
+        r#"(
+            SELECT * FROM (
+                SELECT i.*, ROW_NUMBER() OVER (
+                    PARTITION BY COALESCE(i.github_repo, i.github_url)
+                    ORDER BY i.paper_id <> $1, i.deleted_at IS NOT NULL
+                ) AS copy
+                FROM implementations i
+                JOIN papers v ON v.id = i.paper_id
+                WHERE {}
+                  AND ($2 OR (i.deleted_at IS NULL AND v.deleted_at IS NULL))
+            ) ranked
+            WHERE copy = 1
+        ) AS implementations"#,
+        crate::SAME_ARXIV_PAPER
+    )
+}
+
+/// Those implementations ranked by `IMPLEMENTATION_RANKING`.
+pub(crate) fn ranked_paper_implementations() -> String {
+    format!(
+        r#"
+        SELECT ROW_NUMBER() OVER (ORDER BY {0}) AS rank, {1}
+        FROM {2}
+        ORDER BY rank
+        "#,
+        crate::IMPLEMENTATION_RANKING,
+        IMPLEMENTATION_COLUMNS,
+        paper_implementations()
+    )
+}
+
+/// Authors of paper `$1` with an ORCID iD. An ORCID found on any version
+/// fills in that author's position.
+pub(crate) fn paper_author_orcids() -> String {
+    format!(
+        r#"
+        SELECT position, name, orcid FROM (
+            SELECT pa.position, pa.name, pa.orcid, ROW_NUMBER() OVER (
+                PARTITION BY pa.position
+                ORDER BY pa.orcid IS NULL, pa.paper_id <> $1
+            ) AS copy
+            FROM paper_authors pa
+            JOIN papers v ON v.id = pa.paper_id
+            WHERE {}
+        ) authors
+        WHERE copy = 1
+        ORDER BY position
+        "#,
+        crate::SAME_ARXIV_PAPER
+    )
+}
+
+/// Name of ORCID `$1` on their most recent paper; `$2` includes soft-deleted papers.
+pub(crate) const AUTHOR_NAME: &str = r#"
+    SELECT pa.name
+    FROM paper_authors pa
+    JOIN papers p ON p.id = pa.paper_id
+    WHERE pa.orcid = $1 AND ($2 OR p.deleted_at IS NULL)
+    ORDER BY p.published_date DESC NULLS LAST
+    LIMIT 1
+"#;
+
+/// Papers of ORCID `$1`, each once as its latest arXiv version, wherever the
+/// ORCID was found. `$2` includes soft-deleted papers; `$3`/`$4` paginate.
+pub(crate) fn author_papers() -> String {
+    format!(
+        r#"
+        SELECT id, title, arxiv_id, published_date
+        FROM papers
+        WHERE ($2 OR deleted_at IS NULL)
+          AND {}
+          AND EXISTS (
+            SELECT 1 FROM paper_authors pa
+            JOIN papers v ON v.id = pa.paper_id
+            WHERE pa.orcid = $1
+              AND (v.id = papers.id OR v.arxiv_base_id = papers.arxiv_base_id)
+        )
+        ORDER BY published_date DESC NULLS LAST, id
+        LIMIT $3 OFFSET $4
+        "#,
+        crate::latest_arxiv_version_filter()
+    )
+}
+
+/// Dataset `$1`; `$2` includes soft-deleted rows.
+pub(crate) fn dataset_by_id() -> String {
+    format!(
+        "SELECT {} FROM datasets WHERE id = $1 AND ($2 OR deleted_at IS NULL)",
+        DATASET_COLUMNS
+    )
+}
+
+pub(crate) const DATASET_FILES: &str = r#"
+    SELECT id, dataset_id, url, format, size_bytes, sha256, source, created_at, updated_at
+    FROM dataset_files
+    WHERE dataset_id = $1
+    ORDER BY url
+"#;
+
+/// Benchmark `$1`; `$2` includes soft-deleted rows.
+pub(crate) fn benchmark_by_id() -> String {
+    format!(
+        "SELECT {} FROM benchmarks WHERE id = $1 AND ($2 OR deleted_at IS NULL)",
+        BENCHMARK_COLUMNS
+    )
+}
+
+/// Tasks with rolled-up counts, limited to the subtree under `$1` if set.
+pub(crate) const TASK_NODES: &str = r#"
+    WITH RECURSIVE subtasks (root_id, id) AS (
+        SELECT id, id FROM tasks
+        UNION ALL
+        SELECT s.root_id, t.id FROM tasks t JOIN subtasks s ON t.parent_id = s.id
+    ),
+    scope AS (
+        SELECT id FROM subtasks WHERE $1 IS NULL OR root_id = $1
+    )
+    SELECT t.id, t.name, t.parent_id,
+           COUNT(DISTINCT b.id) AS benchmark_count,
+           COUNT(DISTINCT p.id) AS paper_count
+    FROM tasks t
+    JOIN subtasks s ON s.root_id = t.id
+    JOIN tasks d ON d.id = s.id
+    LEFT JOIN benchmarks b ON b.task = d.name AND b.deleted_at IS NULL
+    LEFT JOIN benchmark_results br ON br.benchmark_id = b.id AND br.deleted_at IS NULL
+    LEFT JOIN papers p ON p.id = br.paper_id AND p.deleted_at IS NULL
+    WHERE t.id IN (SELECT id FROM scope)
+    GROUP BY t.id
+    ORDER BY t.name
+"#;
+
+/// Ancestors of task `$1`, root first.
+pub(crate) const TASK_ANCESTORS: &str = r#"
+    WITH RECURSIVE ancestors (id, depth) AS (
+        SELECT parent_id, 1 FROM tasks WHERE id = $1 AND parent_id IS NOT NULL
+        UNION ALL
+        SELECT t.parent_id, a.depth + 1 FROM tasks t
+        JOIN ancestors a ON t.id = a.id
+        WHERE t.parent_id IS NOT NULL
+    )
+    SELECT t.id, t.name FROM ancestors a JOIN tasks t ON t.id = a.id
+    ORDER BY a.depth DESC
+"#;
+
+/// Find-or-create the task named `$1`.
+pub(crate) const UPSERT_TASK: &str = r#"
+    INSERT INTO tasks (name) VALUES ($1)
+    ON CONFLICT (name) DO UPDATE SET name = excluded.name
+    RETURNING id
+"#;
+
+/// Implementations filtered by reproducibility (`$3`) and archived status
+/// (`$4`); `$5` includes soft-deleted rows, `$1`/`$2` paginate.
+pub(crate) fn implementations() -> String {
+    format!(
+        r#"
+        SELECT {}
+        FROM implementations
+        WHERE ($3 IS NULL
+               OR (COALESCE(has_dockerfile, FALSE)
+                   OR COALESCE(has_conda_env, FALSE)
+                   OR COALESCE(has_pinned_requirements, FALSE)) = $3)
+          AND ($4 IS NULL OR COALESCE(archived, FALSE) = $4)
+          AND ($5 OR deleted_at IS NULL)
+        -- Archived and disabled repos sink below maintained ones
+        ORDER BY (COALESCE(archived, FALSE) OR COALESCE(disabled, FALSE)),
+                 stars DESC NULLS LAST
+        LIMIT $1 OFFSET $2
+        "#,
+        IMPLEMENTATION_COLUMNS
+    )
+}
+
+/// Implementation `$1`; `$2` includes soft-deleted rows.
+pub(crate) fn implementation_by_id() -> String {
+    format!(
+        "SELECT {} FROM implementations WHERE id = $1 AND ($2 OR deleted_at IS NULL)",
+        IMPLEMENTATION_COLUMNS
+    )
+}
+
+/// Packages of implementation `$1`, with the command that installs each.
+pub(crate) const IMPLEMENTATION_PACKAGES: &str = r#"
+    SELECT id, implementation_id, registry, name, url,
+           CASE registry
+               WHEN 'pypi' THEN 'pip install ' || name
+               WHEN 'crates' THEN 'cargo add ' || name
+               WHEN 'npm' THEN 'npm install ' || name
+           END AS install_command,
+           created_at, updated_at
+    FROM packages WHERE implementation_id = $1
+    ORDER BY registry, name
+"#;
+
+pub(crate) const IMPLEMENTATION_DEPENDENCIES: &str = r#"
+    SELECT name, version_spec, source
+    FROM implementation_dependencies WHERE implementation_id = $1
+    ORDER BY name
+"#;
+
+/// Papers whose code on any arXiv version depends on package `$1`, each once
+/// as its latest version. `$4` limits to official code, `$5` includes
+/// soft-deleted rows, `$2`/`$3` paginate.
+pub(crate) fn papers_by_dependency() -> String {
+    format!(
+        r#"
+        SELECT id, title, arxiv_id, published_date
+        FROM papers
+        WHERE ($5 OR deleted_at IS NULL)
+          AND {}
+          AND EXISTS (
+            SELECT 1 FROM implementations i
+            JOIN papers v ON v.id = i.paper_id
+            JOIN implementation_dependencies d ON d.implementation_id = i.id
+            WHERE (v.id = papers.id OR v.arxiv_base_id = papers.arxiv_base_id)
+              AND d.name = $1
+              AND ($4 IS NULL OR COALESCE(i.is_official, FALSE) = $4)
+              AND ($5 OR (i.deleted_at IS NULL AND v.deleted_at IS NULL))
+        )
+        ORDER BY published_date DESC NULLS LAST
+        LIMIT $2 OFFSET $3
+        "#,
+        crate::latest_arxiv_version_filter()
+    )
+}
+
+/// Apply webhook metadata (`$2`..`$5`, each kept when NULL) to the
+/// implementations of GitHub repo `$1`.
+pub(crate) const UPDATE_GITHUB_REPO: &str = r#"
+    UPDATE implementations
+    SET stars = COALESCE($2, stars),
+        pushed_at = COALESCE($3, pushed_at),
+        archived = COALESCE($4, archived),
+        disabled = COALESCE($5, disabled)
+    WHERE github_repo = lower($1)
+"#;
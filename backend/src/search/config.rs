@@ -0,0 +1,61 @@
+//! Index writer tuning, read from `TANTIVY_*` environment variables.
+
+use std::env;
+use std::str::FromStr;
+use tantivy::merge_policy::LogMergePolicy;
+
+/// Writer heap, merge policy and disk budget for a [`SearchIndex`].
+///
+/// [`SearchIndex`]: crate::search::SearchIndex
+#[derive(Clone, Debug)]
+pub struct IndexConfig {
+    /// Memory an index writer buffers before flushing a segment
+    /// (`TANTIVY_WRITER_HEAP_MB`, default 50)
+    pub writer_heap_bytes: usize,
+    /// How many similarly sized segments are merged at once; higher values
+    /// merge less often (`TANTIVY_MERGE_MIN_SEGMENTS`, default 8)
+    pub merge_min_segments: usize,
+    /// Segments with more documents are never merged further
+    /// (`TANTIVY_MERGE_MAX_DOCS`, default 10,000,000)
+    pub merge_max_docs: usize,
+    /// Writes fail once the index directory grows past this
+    /// (`TANTIVY_MAX_DISK_MB`, unlimited when unset)
+    pub max_disk_bytes: Option<u64>,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self {
+            writer_heap_bytes: 50_000_000,
+            merge_min_segments: 8,
+            merge_max_docs: 10_000_000,
+            max_disk_bytes: None,
+        }
+    }
+}
+
+fn env_number<T: FromStr>(name: &str) -> Option<T> {
+    env::var(name).ok()?.trim().parse().ok()
+}
+
+impl IndexConfig {
+    /// Defaults overridden by whichever `TANTIVY_*` variables are set.
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            writer_heap_bytes: env_number::<usize>("TANTIVY_WRITER_HEAP_MB")
+                .map_or(default.writer_heap_bytes, |mb| mb * 1_000_000),
+            merge_min_segments: env_number("TANTIVY_MERGE_MIN_SEGMENTS")
+                .unwrap_or(default.merge_min_segments),
+            merge_max_docs: env_number("TANTIVY_MERGE_MAX_DOCS").unwrap_or(default.merge_max_docs),
+            max_disk_bytes: env_number::<u64>("TANTIVY_MAX_DISK_MB").map(|mb| mb * 1_000_000),
+        }
+    }
+
+    pub fn merge_policy(&self) -> LogMergePolicy {
+        let mut policy = LogMergePolicy::default();
+        policy.set_min_num_segments(self.merge_min_segments);
+        policy.set_max_docs_before_merge(self.merge_max_docs);
+        policy
+    }
+}
@@ -1,10 +1,12 @@
 //! Tantivy index management and document conversion.
 
 use anyhow::{Context, Result};
-use std::path::Path;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
 use tantivy::schema::Schema;
 use tantivy::{Index, IndexReader, IndexWriter, TantivyDocument};
 
+use crate::search::config::IndexConfig;
 use crate::search::schema::{create_paper_schema, PaperFields};
 use crate::Paper;
 
@@ -14,6 +16,19 @@ pub struct SearchIndex {
     pub reader: IndexReader,
     pub schema: Schema,
     pub fields: PaperFields,
+    /// Directory holding the index files
+    pub path: PathBuf,
+    pub config: IndexConfig,
+}
+
+/// Size and shape of the index on disk.
+#[derive(Serialize, Debug, Clone)]
+pub struct IndexStatus {
+    pub num_docs: u64,
+    pub num_segments: usize,
+    pub disk_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_disk_bytes: Option<u64>,
 }
 
 impl SearchIndex {
@@ -23,6 +38,11 @@ impl SearchIndex {
 
         let index = Index::open_in_dir(path.as_ref())
             .with_context(|| format!("Failed to open index at {:?}", path.as_ref()))?;
+        anyhow::ensure!(
+            index.schema() == schema,
+            "Index at {:?} has an outdated schema; rebuild it with `build_search_index --force`",
+            path.as_ref()
+        );
 
         // Register the English stemming tokenizer
         let tokenizer_manager = index.tokenizers();
@@ -46,6 +66,8 @@ impl SearchIndex {
             reader,
             schema,
             fields,
+            path: path.as_ref().to_path_buf(),
+            config: IndexConfig::default(),
         })
     }
 
@@ -81,6 +103,8 @@ impl SearchIndex {
             reader,
             schema,
             fields,
+            path: path.as_ref().to_path_buf(),
+            config: IndexConfig::default(),
         })
     }
 
@@ -93,11 +117,97 @@ impl SearchIndex {
         }
     }
 
-    /// Create an IndexWriter with the given heap size (in bytes).
-    pub fn writer(&self, heap_size: usize) -> Result<IndexWriter> {
-        self.index
-            .writer(heap_size)
-            .context("Failed to create index writer")
+    /// Use the given writer heap, merge policy and disk budget.
+    pub fn with_config(mut self, config: IndexConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Create an IndexWriter with the configured heap size and merge policy.
+    pub fn writer(&self) -> Result<IndexWriter> {
+        let writer = self
+            .index
+            .writer(self.config.writer_heap_bytes)
+            .context("Failed to create index writer")?;
+        writer.set_merge_policy(Box::new(self.config.merge_policy()));
+        Ok(writer)
+    }
+
+    /// Total size of the files in the index directory.
+    pub fn disk_usage(&self) -> Result<u64> {
+        let mut total = 0;
+        for entry in std::fs::read_dir(&self.path)
+            .with_context(|| format!("Failed to read index directory {:?}", self.path))?
+        {
+            let metadata = entry?.metadata()?;
+            if metadata.is_file() {
+                total += metadata.len();
+            }
+        }
+        Ok(total)
+    }
+
+    /// Remove files left behind by merges, then fail if the index is still
+    /// over its disk budget. Call after each commit of a large write.
+    pub fn enforce_disk_budget(&self, writer: &IndexWriter) -> Result<()> {
+        let Some(max_disk_bytes) = self.config.max_disk_bytes else {
+            return Ok(());
+        };
+
+        writer
+            .garbage_collect_files()
+            .wait()
+            .context("Failed to remove stale index files")?;
+        let disk_bytes = self.disk_usage()?;
+        anyhow::ensure!(
+            disk_bytes <= max_disk_bytes,
+            "Index uses {} MB, over its {} MB budget (TANTIVY_MAX_DISK_MB)",
+            disk_bytes / 1_000_000,
+            max_disk_bytes / 1_000_000
+        );
+        Ok(())
+    }
+
+    pub fn status(&self) -> Result<IndexStatus> {
+        let segments = self
+            .index
+            .searchable_segment_metas()
+            .context("Failed to read index metadata")?;
+
+        Ok(IndexStatus {
+            num_docs: segments.iter().map(|segment| segment.num_docs() as u64).sum(),
+            num_segments: segments.len(),
+            disk_bytes: self.disk_usage()?,
+            max_disk_bytes: self.config.max_disk_bytes,
+        })
+    }
+
+    /// Merge all segments into one and delete the files they replace, which
+    /// speeds up search and reclaims space after incremental writes. Blocks
+    /// until done, and fails if another process holds the writer lock.
+    pub fn optimize(&self) -> Result<IndexStatus> {
+        let mut writer = self.writer()?;
+
+        let segment_ids = self
+            .index
+            .searchable_segment_ids()
+            .context("Failed to read index metadata")?;
+        if segment_ids.len() > 1 {
+            writer
+                .merge(&segment_ids)
+                .wait()
+                .context("Failed to merge segments")?;
+        }
+        writer
+            .garbage_collect_files()
+            .wait()
+            .context("Failed to remove stale index files")?;
+        writer
+            .wait_merging_threads()
+            .context("Failed to finish merging")?;
+
+        self.reader.reload().context("Failed to reload index reader")?;
+        self.status()
     }
 
     /// Convert a Paper to a Tantivy document.
@@ -105,12 +215,12 @@ impl SearchIndex {
         let mut doc = TantivyDocument::new();
 
         // ID (stored for lookup)
-        doc.add_text(self.fields.id, &paper.id.to_string());
+        doc.add_text(self.fields.id, paper.id.to_string());
 
         // Full-text fields
         doc.add_text(self.fields.title, &paper.title);
 
-        if let Some(ref abstract_text) = paper.r#abstract {
+        if let Some(abstract_text) = paper.abstract_plain.as_ref().or(paper.r#abstract.as_ref()) {
             doc.add_text(self.fields.abstract_field, abstract_text);
         }
 
@@ -121,7 +231,7 @@ impl SearchIndex {
                     .iter()
                     .filter_map(|v| v.as_str().map(|s| s.to_string()))
                     .collect();
-                doc.add_text(self.fields.authors, &authors_text.join(" "));
+                doc.add_text(self.fields.authors, authors_text.join(" "));
             }
         }
 
@@ -130,16 +240,20 @@ impl SearchIndex {
             doc.add_text(self.fields.arxiv_id, arxiv_id);
         }
 
-        // Date field
-        if let Some(date) = paper.published_date {
-            let datetime = tantivy::DateTime::from_timestamp_secs(
-                date.and_hms_opt(0, 0, 0)
-                    .map(|dt| dt.and_utc().timestamp())
-                    .unwrap_or(0),
-            );
+        // Date field (exact publication time when known)
+        let timestamp = paper.published_at.map(|at| at.timestamp()).or_else(|| {
+            paper
+                .published_date
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|dt| dt.and_utc().timestamp())
+        });
+        if let Some(timestamp) = timestamp {
+            let datetime = tantivy::DateTime::from_timestamp_secs(timestamp);
             doc.add_date(self.fields.published_date, datetime);
         }
 
+        doc.add_bool(self.fields.has_code, paper.has_code);
+
         doc
     }
 }
@@ -159,7 +273,10 @@ impl Clone for SearchIndex {
                 authors: self.fields.authors,
                 arxiv_id: self.fields.arxiv_id,
                 published_date: self.fields.published_date,
+                has_code: self.fields.has_code,
             },
+            path: self.path.clone(),
+            config: self.config.clone(),
         }
     }
 }
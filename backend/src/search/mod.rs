@@ -1,9 +1,11 @@
 //! Tantivy full-text search module for papers.
 
+pub mod config;
 pub mod index;
 pub mod query;
 pub mod schema;
 
-pub use index::SearchIndex;
+pub use config::IndexConfig;
+pub use index::{IndexStatus, SearchIndex};
 pub use query::{SearchParams, SearchResponse, SearchFacets, DateBucket};
 pub use schema::create_paper_schema;
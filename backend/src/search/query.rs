@@ -5,10 +5,9 @@ use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tantivy::collector::TopDocs;
-use tantivy::query::{BooleanQuery, Occur, Query, QueryParser, RangeQuery};
-use tantivy::schema::Field;
-use tantivy::schema::Value;
-use tantivy::{DateTime, Searcher, TantivyDocument};
+use tantivy::query::{BooleanQuery, Occur, Query, QueryParser, RangeQuery, TermQuery};
+use tantivy::schema::{Field, IndexRecordOption, Value};
+use tantivy::{DateTime, Searcher, TantivyDocument, Term};
 
 use crate::search::index::SearchIndex;
 
@@ -29,8 +28,12 @@ pub struct SearchParams {
     pub date_from: Option<NaiveDate>,
     /// Filter: papers published on or before this date
     pub date_to: Option<NaiveDate>,
+    /// Filter: only papers with (or without) an implementation
+    pub has_code: Option<bool>,
     /// Legacy search param (maps to q)
     pub search: Option<String>,
+    /// Language code for translated titles/abstracts (e.g. de)
+    pub lang: Option<String>,
 }
 
 impl SearchParams {
@@ -91,21 +94,28 @@ pub fn search_papers(
         .parse_query(query_str)
         .context("Failed to parse search query")?;
 
-    // Apply date range filter if provided
-    let final_query: Box<dyn Query> =
-        if params.date_from.is_some() || params.date_to.is_some() {
-            let range_query = build_date_range_query(
-                fields.published_date,
-                params.date_from,
-                params.date_to,
-            );
-            Box::new(BooleanQuery::new(vec![
-                (Occur::Must, text_query),
-                (Occur::Must, range_query),
-            ]))
-        } else {
-            text_query
-        };
+    // Apply date range and has_code filters if provided
+    let mut clauses = vec![(Occur::Must, text_query)];
+    if params.date_from.is_some() || params.date_to.is_some() {
+        let range_query = build_date_range_query(
+            fields.published_date,
+            params.date_from,
+            params.date_to,
+        );
+        clauses.push((Occur::Must, range_query));
+    }
+    if let Some(has_code) = params.has_code {
+        let term = Term::from_field_bool(fields.has_code, has_code);
+        clauses.push((
+            Occur::Must,
+            Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
+        ));
+    }
+    let final_query: Box<dyn Query> = if clauses.len() > 1 {
+        Box::new(BooleanQuery::new(clauses))
+    } else {
+        clauses.pop().unwrap().1
+    };
 
     // Execute search - fetch more than needed to get total count
     let top_docs = searcher
@@ -195,7 +205,7 @@ fn collect_date_facets(
         .collect();
 
     // Sort by date descending
-    date_histogram.sort_by(|a, b| (b.year, b.month).cmp(&(a.year, a.month)));
+    date_histogram.sort_by_key(|b| std::cmp::Reverse((b.year, b.month)));
 
     Ok(SearchFacets { date_histogram })
 }
@@ -13,6 +13,7 @@ pub struct PaperFields {
     pub authors: Field,
     pub arxiv_id: Field,
     pub published_date: Field,
+    pub has_code: Field,
 }
 
 /// Create the Tantivy schema for papers.
@@ -41,6 +42,9 @@ pub fn create_paper_schema() -> (Schema, PaperFields) {
     // Date field for faceted search (FAST enables efficient range queries)
     let published_date = schema_builder.add_date_field("published_date", INDEXED | STORED | FAST);
 
+    // Whether the paper has an implementation, for filtering
+    let has_code = schema_builder.add_bool_field("has_code", INDEXED | STORED | FAST);
+
     let schema = schema_builder.build();
 
     let fields = PaperFields {
@@ -50,6 +54,7 @@ pub fn create_paper_schema() -> (Schema, PaperFields) {
         authors,
         arxiv_id,
         published_date,
+        has_code,
     };
 
     (schema, fields)
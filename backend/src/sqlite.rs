@@ -0,0 +1,940 @@
+//! SQLite mode for local development.
+//!
+//! With `DATABASE_URL=sqlite://data/cwp.db`, `cargo run` serves the API from
+//! an SQLite file instead of Postgres, and `cargo run --bin seed` fills it
+//! with a small sample. [`SqliteRepository`] runs the same queries as
+//! [`crate::postgres::PgRepository`] in SQLite's dialect, behind the same
+//! handlers. The differences:
+//!
+//! - search is a case-insensitive `LIKE` on title and abstract (no Tantivy)
+//! - there is no read replica, and `/api/papers/stream` answers 501 since
+//!   SQLite has no `LISTEN`/`NOTIFY`
+//!
+//! The schema lives in `migrations_sqlite/` and is applied on connect.
+
+use crate::repository::*;
+use crate::{
+    arxiv_base_id, github_repo, latest_arxiv_version_filter, ArxivVersion, Benchmark,
+    BenchmarkResult, Dataset, DatasetFile, Dependency, Implementation, LanguageBreakdown,
+    Package, Paper, PaperAuthor, PaperSummary, PaperTranslation, RankedImplementation, TaskNode,
+    TaskSummary, ARXIV_LATEST_FIRST,
+};
+use anyhow::{Context, Result};
+use axum::async_trait;
+use futures::stream::BoxStream;
+use serde::Deserialize;
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::types::Json as SqlJson;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Schema for SQLite mode, embedded so `cargo run` can apply it.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations_sqlite");
+
+/// Sample catalog loaded by [`seed`].
+const SAMPLE: &str = include_str!("../seed/sample.json");
+
+/// Whether a database URL selects SQLite mode.
+pub fn is_sqlite_url(url: &str) -> bool {
+    url.starts_with("sqlite:")
+}
+
+/// Open the SQLite database at `url`, creating the file (and its directory)
+/// if needed, and apply the SQLite migrations.
+pub async fn connect(url: &str) -> Result<SqlitePool> {
+    let options = SqliteConnectOptions::from_str(url)
+        .with_context(|| format!("Invalid SQLite URL {}", url))?
+        .create_if_missing(true)
+        .foreign_keys(true);
+
+    let filename = options.clone().get_filename();
+    if let Some(dir) = filename.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory {:?}", dir))?;
+    }
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(5)
+        .connect_with(options)
+        .await
+        .with_context(|| format!("Failed to open {}", url))?;
+
+    MIGRATOR
+        .run(&pool)
+        .await
+        .context("Failed to apply SQLite migrations")?;
+
+    Ok(pool)
+}
+
+// ============================================================================
+// Seed Data
+// ============================================================================
+
+#[derive(Deserialize)]
+struct Sample {
+    datasets: Vec<SampleDataset>,
+    benchmarks: Vec<SampleBenchmark>,
+    papers: Vec<SamplePaper>,
+}
+
+#[derive(Deserialize)]
+struct SampleDataset {
+    name: String,
+    description: String,
+    modalities: Vec<String>,
+    homepage_url: String,
+}
+
+#[derive(Deserialize)]
+struct SampleBenchmark {
+    name: String,
+    dataset: String,
+    task: String,
+}
+
+#[derive(Deserialize)]
+struct SamplePaper {
+    title: String,
+    arxiv_id: String,
+    r#abstract: String,
+    published_date: chrono::NaiveDate,
+    authors: Vec<String>,
+    implementations: Vec<SampleImplementation>,
+    results: Vec<SampleResult>,
+}
+
+#[derive(Deserialize)]
+struct SampleImplementation {
+    github_url: String,
+    framework: String,
+    stars: i32,
+    #[serde(default)]
+    is_official: bool,
+    #[serde(default)]
+    archived: bool,
+    languages: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct SampleResult {
+    benchmark: String,
+    metric_name: String,
+    /// Decimal string, stored as-is like Postgres NUMERIC
+    metric_value: String,
+}
+
+/// Rows written by [`seed`].
+#[derive(Debug, Default)]
+pub struct SeedStats {
+    pub datasets: usize,
+    pub benchmarks: usize,
+    pub papers: usize,
+    pub implementations: usize,
+    pub results: usize,
+}
+
+/// Upsert the sample catalog in `seed/sample.json`: ten well-known papers
+/// (ViT in two arXiv versions) with their implementations, datasets and
+/// leaderboard results. Safe to run repeatedly.
+pub async fn seed(pool: &SqlitePool) -> Result<SeedStats> {
+    let sample: Sample = serde_json::from_str(SAMPLE).context("Invalid seed/sample.json")?;
+    let mut stats = SeedStats::default();
+    let mut tx = pool.begin().await?;
+
+    let mut dataset_ids = HashMap::new();
+    for dataset in &sample.datasets {
+        let id: uuid::Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO datasets (id, name, description, modalities, homepage_url)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (name) DO UPDATE SET
+                description = excluded.description,
+                modalities = excluded.modalities,
+                homepage_url = excluded.homepage_url
+            RETURNING id
+            "#,
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(&dataset.name)
+        .bind(&dataset.description)
+        .bind(SqlJson(&dataset.modalities))
+        .bind(&dataset.homepage_url)
+        .fetch_one(&mut *tx)
+        .await
+        .with_context(|| format!("Failed to seed dataset {}", dataset.name))?;
+        dataset_ids.insert(dataset.name.as_str(), id);
+        stats.datasets += 1;
+    }
+
+    let mut benchmark_ids = HashMap::new();
+    for benchmark in &sample.benchmarks {
+        let dataset_id = dataset_ids
+            .get(benchmark.dataset.as_str())
+            .with_context(|| format!("Unknown dataset {}", benchmark.dataset))?;
+        let id: uuid::Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO benchmarks (id, name, dataset_id, task)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (name, dataset_id) DO UPDATE SET task = excluded.task
+            RETURNING id
+            "#,
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(&benchmark.name)
+        .bind(dataset_id)
+        .bind(&benchmark.task)
+        .fetch_one(&mut *tx)
+        .await
+        .with_context(|| format!("Failed to seed benchmark {}", benchmark.name))?;
+        benchmark_ids.insert(benchmark.name.as_str(), id);
+        stats.benchmarks += 1;
+    }
+
+    for paper in &sample.papers {
+        // Postgres derives these in generated columns
+        let base_id = arxiv_base_id(&paper.arxiv_id);
+        let version: Option<i32> = paper
+            .arxiv_id
+            .rsplit_once('v')
+            .and_then(|(_, version)| version.parse().ok());
+
+        let paper_id: uuid::Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO papers (id, title, abstract, abstract_plain, arxiv_id, arxiv_base_id,
+                                arxiv_version, arxiv_url, pdf_url, published_date, authors)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (arxiv_id) DO UPDATE SET
+                title = excluded.title,
+                abstract = excluded.abstract,
+                abstract_plain = excluded.abstract_plain,
+                published_date = excluded.published_date,
+                authors = excluded.authors
+            RETURNING id
+            "#,
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(&paper.title)
+        .bind(&paper.r#abstract)
+        .bind(crate::text::clean_abstract(&paper.r#abstract))
+        .bind(&paper.arxiv_id)
+        .bind(base_id)
+        .bind(version)
+        .bind(format!("https://arxiv.org/abs/{}", paper.arxiv_id))
+        .bind(format!("https://arxiv.org/pdf/{}", paper.arxiv_id))
+        .bind(paper.published_date)
+        .bind(SqlJson(&paper.authors))
+        .fetch_one(&mut *tx)
+        .await
+        .with_context(|| format!("Failed to seed paper {}", paper.arxiv_id))?;
+        stats.papers += 1;
+
+        for implementation in &paper.implementations {
+            sqlx::query(
+                r#"
+                INSERT INTO implementations (id, paper_id, github_url, github_repo, framework,
+                                             stars, is_official, archived, languages)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT (paper_id, github_url) DO UPDATE SET
+                    framework = excluded.framework,
+                    stars = excluded.stars,
+                    is_official = excluded.is_official,
+                    archived = excluded.archived,
+                    languages = excluded.languages
+                "#,
+            )
+            .bind(uuid::Uuid::new_v4())
+            .bind(paper_id)
+            .bind(&implementation.github_url)
+            .bind(github_repo(&implementation.github_url))
+            .bind(&implementation.framework)
+            .bind(implementation.stars)
+            .bind(implementation.is_official)
+            .bind(implementation.archived)
+            .bind(SqlJson(&implementation.languages))
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("Failed to seed {}", implementation.github_url))?;
+            stats.implementations += 1;
+        }
+
+        for result in &paper.results {
+            let benchmark_id = benchmark_ids
+                .get(result.benchmark.as_str())
+                .with_context(|| format!("Unknown benchmark {}", result.benchmark))?;
+            rust_decimal::Decimal::from_str(&result.metric_value)
+                .with_context(|| format!("Invalid metric value {}", result.metric_value))?;
+
+            sqlx::query(
+                r#"
+                INSERT INTO benchmark_results (id, paper_id, benchmark_id, metric_name,
+                                               metric_value)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (paper_id, benchmark_id, metric_name) DO UPDATE SET
+                    metric_value = excluded.metric_value
+                "#,
+            )
+            .bind(uuid::Uuid::new_v4())
+            .bind(paper_id)
+            .bind(benchmark_id)
+            .bind(&result.metric_name)
+            .bind(&result.metric_value)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("Failed to seed a result of {}", paper.arxiv_id))?;
+            stats.results += 1;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(stats)
+}
+
+// ============================================================================
+// Stored Rows
+// ============================================================================
+
+/// A `datasets` row as stored in SQLite, with list columns as JSON text.
+#[derive(sqlx::FromRow)]
+struct DatasetRow {
+    id: uuid::Uuid,
+    name: String,
+    description: Option<String>,
+    modalities: Option<SqlJson<Vec<String>>>,
+    task_categories: Option<SqlJson<Vec<String>>>,
+    languages: Option<SqlJson<Vec<String>>>,
+    size: Option<String>,
+    homepage_url: Option<String>,
+    github_url: Option<String>,
+    paper_url: Option<String>,
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+    updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<DatasetRow> for Dataset {
+    fn from(row: DatasetRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            description: row.description,
+            modalities: row.modalities.map(|SqlJson(list)| list),
+            task_categories: row.task_categories.map(|SqlJson(list)| list),
+            languages: row.languages.map(|SqlJson(list)| list),
+            size: row.size,
+            homepage_url: row.homepage_url,
+            github_url: row.github_url,
+            paper_url: row.paper_url,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            deleted_at: row.deleted_at,
+        }
+    }
+}
+
+/// A `benchmark_results` row as stored in SQLite, with the metric value as
+/// decimal text.
+#[derive(sqlx::FromRow)]
+struct BenchmarkResultRow {
+    id: uuid::Uuid,
+    paper_id: Option<uuid::Uuid>,
+    benchmark_id: Option<uuid::Uuid>,
+    implementation_id: Option<uuid::Uuid>,
+    metric_name: String,
+    metric_value: String,
+    extra_data: Option<serde_json::Value>,
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
+    updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TryFrom<BenchmarkResultRow> for BenchmarkResult {
+    type Error = rust_decimal::Error;
+
+    fn try_from(row: BenchmarkResultRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            paper_id: row.paper_id,
+            benchmark_id: row.benchmark_id,
+            implementation_id: row.implementation_id,
+            metric_name: row.metric_name,
+            metric_value: rust_decimal::Decimal::from_str(&row.metric_value)?,
+            extra_data: row.extra_data,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            deleted_at: row.deleted_at,
+        })
+    }
+}
+
+
+// ============================================================================
+// Repository
+// ============================================================================
+
+/// When a paper was published, for "latest papers" ordering: `published_at`,
+/// else midnight UTC on `published_date`, as a Julian day so the two compare.
+const PUBLICATION_TIME: &str = "COALESCE(julianday(published_at), julianday(published_date))";
+
+/// UUIDs as a JSON list of hex strings, for `IN (SELECT unhex(value) FROM
+/// json_each($1))` since SQLite can't bind arrays.
+fn uuid_list(ids: &[uuid::Uuid]) -> SqlJson<Vec<String>> {
+    SqlJson(ids.iter().map(|id| id.simple().to_string()).collect())
+}
+
+#[derive(Clone, Debug)]
+pub struct SqliteRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl Repository for SqliteRepository {
+    fn primary(&self) -> Box<dyn Repository> {
+        Box::new(self.clone())
+    }
+
+    async fn catalog_counts(&self) -> sqlx::Result<CatalogCounts> {
+        sqlx::query_as(CATALOG_COUNTS).fetch_one(&self.pool).await
+    }
+
+    async fn last_ingested_at(
+        &self,
+    ) -> sqlx::Result<Vec<(String, chrono::DateTime<chrono::Utc>)>> {
+        sqlx::query_as(LAST_INGESTED_AT).fetch_all(&self.pool).await
+    }
+
+    async fn papers(&self, query: &PaperQuery) -> sqlx::Result<Vec<Paper>> {
+        let order = if query.ascending { "ASC" } else { "DESC" };
+
+        sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM papers
+            WHERE ($1 IS NULL OR title LIKE $1 OR COALESCE(abstract_plain, abstract) LIKE $1)
+              AND ($4 OR deleted_at IS NULL)
+              AND ($5 IS NULL OR has_code = $5)
+              AND {}
+            ORDER BY {} {order} NULLS LAST, id {order}
+            LIMIT $2 OFFSET $3
+            "#,
+            PAPER_COLUMNS,
+            latest_arxiv_version_filter(),
+            PUBLICATION_TIME,
+        ))
+        .bind(query.search.as_ref().map(|search| format!("%{}%", search)))
+        .bind(query.limit)
+        .bind(query.offset)
+        .bind(query.include_deleted)
+        .bind(query.has_code)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn papers_by_ids(
+        &self,
+        ids: &[uuid::Uuid],
+        include_deleted: bool,
+    ) -> sqlx::Result<Vec<Paper>> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT {} FROM papers
+            WHERE id IN (SELECT unhex(value) FROM json_each($1))
+              AND ($2 OR deleted_at IS NULL)
+            "#,
+            PAPER_COLUMNS
+        ))
+        .bind(uuid_list(ids))
+        .bind(include_deleted)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn latest_version_ids(
+        &self,
+        ids: &[uuid::Uuid],
+    ) -> sqlx::Result<Vec<(uuid::Uuid, uuid::Uuid)>> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT hit.id, COALESCE((
+                SELECT v.id FROM papers v
+                WHERE v.arxiv_base_id = hit.arxiv_base_id
+                ORDER BY {}
+                LIMIT 1
+            ), hit.id)
+            FROM papers hit
+            WHERE hit.id IN (SELECT unhex(value) FROM json_each($1))
+            "#,
+            ARXIV_LATEST_FIRST
+        ))
+        .bind(uuid_list(ids))
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn arxiv_versions(
+        &self,
+        base_ids: &[String],
+        include_deleted: bool,
+    ) -> sqlx::Result<Vec<ArxivVersion>> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT arxiv_base_id, id, arxiv_id, arxiv_version AS version, published_date
+            FROM papers
+            WHERE arxiv_base_id IN (SELECT value FROM json_each($1))
+              AND ($2 OR deleted_at IS NULL)
+            ORDER BY {}
+            "#,
+            ARXIV_LATEST_FIRST
+        ))
+        .bind(SqlJson(base_ids))
+        .bind(include_deleted)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn translations(
+        &self,
+        paper_ids: &[uuid::Uuid],
+        language: &str,
+    ) -> sqlx::Result<Vec<PaperTranslation>> {
+        sqlx::query_as(
+            r#"
+            SELECT paper_id, language, title, abstract, provider, updated_at
+            FROM paper_translations
+            WHERE paper_id IN (SELECT unhex(value) FROM json_each($1))
+              AND language = lower($2)
+            "#,
+        )
+        .bind(uuid_list(paper_ids))
+        .bind(language)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn paper_by_id(
+        &self,
+        id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Paper>> {
+        sqlx::query_as(&paper_where("id = $1"))
+            .bind(id)
+            .bind(include_deleted)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn paper_by_doi(&self, doi: &str, include_deleted: bool) -> sqlx::Result<Option<Paper>> {
+        sqlx::query_as(&paper_where("doi = $1"))
+            .bind(doi)
+            .bind(include_deleted)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn paper_by_arxiv_base_id(
+        &self,
+        base_id: &str,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Paper>> {
+        sqlx::query_as(&paper_by_arxiv_base_id())
+            .bind(base_id)
+            .bind(include_deleted)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn paper_implementations(
+        &self,
+        paper_id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Vec<RankedImplementation>> {
+        sqlx::query_as(&ranked_paper_implementations())
+            .bind(paper_id)
+            .bind(include_deleted)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn paper_languages(
+        &self,
+        paper_id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Vec<LanguageBreakdown>> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT lang.key AS language,
+                   SUM(lang.value) AS bytes,
+                   100.0 * SUM(lang.value) / SUM(SUM(lang.value)) OVER () AS percentage
+            FROM {}, json_each(implementations.languages) AS lang
+            GROUP BY lang.key
+            ORDER BY bytes DESC
+            "#,
+            paper_implementations()
+        ))
+        .bind(paper_id)
+        .bind(include_deleted)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn paper_author_orcids(&self, paper_id: uuid::Uuid) -> sqlx::Result<Vec<PaperAuthor>> {
+        sqlx::query_as(&paper_author_orcids())
+            .bind(paper_id)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn author_name(
+        &self,
+        orcid: &str,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<String>> {
+        sqlx::query_scalar(AUTHOR_NAME)
+            .bind(orcid)
+            .bind(include_deleted)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn author_papers(
+        &self,
+        orcid: &str,
+        include_deleted: bool,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<PaperSummary>> {
+        sqlx::query_as(&author_papers())
+            .bind(orcid)
+            .bind(include_deleted)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn datasets(
+        &self,
+        search: Option<&str>,
+        include_deleted: bool,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<Dataset>> {
+        let rows: Vec<DatasetRow> = sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM datasets
+            WHERE ($1 IS NULL OR name LIKE $1 OR description LIKE $1)
+              AND ($4 OR deleted_at IS NULL)
+            ORDER BY name
+            LIMIT $2 OFFSET $3
+            "#,
+            DATASET_COLUMNS
+        ))
+        .bind(search.map(|search| format!("%{}%", search)))
+        .bind(limit)
+        .bind(offset)
+        .bind(include_deleted)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Dataset::from).collect())
+    }
+
+    async fn dataset_by_id(
+        &self,
+        id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Dataset>> {
+        let row: Option<DatasetRow> = sqlx::query_as(&dataset_by_id())
+            .bind(id)
+            .bind(include_deleted)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(Dataset::from))
+    }
+
+    async fn dataset_files(&self, dataset_id: uuid::Uuid) -> sqlx::Result<Vec<DatasetFile>> {
+        sqlx::query_as(DATASET_FILES)
+            .bind(dataset_id)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn benchmarks(
+        &self,
+        search: Option<&str>,
+        include_deleted: bool,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<Benchmark>> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT {}
+            FROM benchmarks
+            WHERE ($1 IS NULL OR name LIKE $1 OR task LIKE $1)
+              AND ($4 OR deleted_at IS NULL)
+            ORDER BY name
+            LIMIT $2 OFFSET $3
+            "#,
+            BENCHMARK_COLUMNS
+        ))
+        .bind(search.map(|search| format!("%{}%", search)))
+        .bind(limit)
+        .bind(offset)
+        .bind(include_deleted)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    async fn benchmark_by_id(
+        &self,
+        id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Benchmark>> {
+        sqlx::query_as(&benchmark_by_id())
+            .bind(id)
+            .bind(include_deleted)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn benchmark_results(
+        &self,
+        include_deleted: bool,
+        limit: i64,
+        offset: i64,
+    ) -> sqlx::Result<Vec<BenchmarkResult>> {
+        let rows: Vec<BenchmarkResultRow> = sqlx::query_as(
+            r#"
+            SELECT id, paper_id, benchmark_id, implementation_id, metric_name,
+                   metric_value, extra_data, created_at, updated_at, deleted_at
+            FROM benchmark_results
+            WHERE ($3 OR deleted_at IS NULL)
+            ORDER BY CAST(metric_value AS REAL) DESC
+            LIMIT $1 OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .bind(include_deleted)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| BenchmarkResult::try_from(row).map_err(|e| sqlx::Error::Decode(e.into())))
+            .collect()
+    }
+
+    async fn leaderboard_top(&self, paper_id: uuid::Uuid) -> sqlx::Result<Option<LeaderboardTop>> {
+        let top: Option<(String, String, String)> = sqlx::query_as(
+            r#"
+            SELECT br.metric_name, br.metric_value, COALESCE(d.name, b.name)
+            FROM benchmark_results br
+            JOIN benchmarks b ON b.id = br.benchmark_id
+            LEFT JOIN datasets d ON d.id = b.dataset_id
+            LEFT JOIN metrics m ON lower(m.name) = lower(br.metric_name)
+            WHERE br.paper_id = $1
+              AND br.deleted_at IS NULL
+              AND b.deleted_at IS NULL
+              AND EXISTS (
+                  SELECT 1 FROM benchmark_results other
+                  WHERE other.benchmark_id = br.benchmark_id
+                    AND other.metric_name = br.metric_name
+                    AND other.paper_id <> br.paper_id
+                    AND other.deleted_at IS NULL
+              )
+              AND NOT EXISTS (
+                  SELECT 1 FROM benchmark_results other
+                  WHERE other.benchmark_id = br.benchmark_id
+                    AND other.metric_name = br.metric_name
+                    AND other.deleted_at IS NULL
+                    AND CASE WHEN COALESCE(m.higher_is_better, TRUE)
+                             THEN CAST(other.metric_value AS REAL)
+                                  > CAST(br.metric_value AS REAL)
+                             ELSE CAST(other.metric_value AS REAL)
+                                  < CAST(br.metric_value AS REAL)
+                        END
+              )
+            ORDER BY (
+                SELECT COUNT(*) FROM benchmark_results c
+                WHERE c.benchmark_id = br.benchmark_id
+                  AND c.metric_name = br.metric_name
+                  AND c.deleted_at IS NULL
+            ) DESC, b.name
+            LIMIT 1
+            "#,
+        )
+        .bind(paper_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        top.map(|(metric_name, metric_value, dataset_name)| {
+            Ok(LeaderboardTop {
+                metric_name,
+                metric_value: rust_decimal::Decimal::from_str(&metric_value)
+                    .map_err(|e| sqlx::Error::Decode(e.into()))?,
+                dataset_name,
+            })
+        })
+        .transpose()
+    }
+
+    async fn task_nodes(&self, root: Option<uuid::Uuid>) -> sqlx::Result<Vec<TaskNode>> {
+        sqlx::query_as(TASK_NODES)
+            .bind(root)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn task_ancestors(&self, id: uuid::Uuid) -> sqlx::Result<Vec<TaskSummary>> {
+        sqlx::query_as(TASK_ANCESTORS)
+            .bind(id)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Checks for cycles itself, as Postgres's `check_task_parent` trigger does.
+    async fn set_task_parent(
+        &self,
+        id: uuid::Uuid,
+        parent: Option<&str>,
+    ) -> sqlx::Result<TaskParentChange> {
+        let mut tx = self.pool.begin().await?;
+
+        let name: Option<String> = sqlx::query_scalar("SELECT name FROM tasks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let Some(name) = name else {
+            return Ok(TaskParentChange::NotFound);
+        };
+
+        let parent_id: Option<uuid::Uuid> = match parent {
+            Some(parent) => Some(
+                sqlx::query_scalar(UPSERT_TASK)
+                    .bind(parent)
+                    .fetch_one(&mut *tx)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        let cycle: bool = sqlx::query_scalar(
+            r#"
+            WITH RECURSIVE ancestors (id) AS (
+                SELECT $2
+                UNION
+                SELECT t.parent_id FROM tasks t
+                JOIN ancestors a ON t.id = a.id
+                WHERE t.parent_id IS NOT NULL
+            )
+            SELECT EXISTS (SELECT 1 FROM ancestors WHERE id = $1)
+            "#,
+        )
+        .bind(id)
+        .bind(parent_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if cycle {
+            return Ok(TaskParentChange::Cycle(format!(
+                "Task \"{}\" cannot be placed under its own subtask",
+                name
+            )));
+        }
+
+        sqlx::query("UPDATE tasks SET parent_id = $2 WHERE id = $1")
+            .bind(id)
+            .bind(parent_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        Ok(TaskParentChange::Updated)
+    }
+
+    async fn implementations(
+        &self,
+        query: &ImplementationQuery,
+    ) -> sqlx::Result<Vec<Implementation>> {
+        sqlx::query_as(&implementations())
+            .bind(query.limit)
+            .bind(query.offset)
+            .bind(query.reproducible)
+            .bind(query.archived)
+            .bind(query.include_deleted)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn implementation_by_id(
+        &self,
+        id: uuid::Uuid,
+        include_deleted: bool,
+    ) -> sqlx::Result<Option<Implementation>> {
+        sqlx::query_as(&implementation_by_id())
+            .bind(id)
+            .bind(include_deleted)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    async fn implementation_packages(
+        &self,
+        implementation_id: uuid::Uuid,
+    ) -> sqlx::Result<Vec<Package>> {
+        sqlx::query_as(IMPLEMENTATION_PACKAGES)
+            .bind(implementation_id)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn implementation_dependencies(
+        &self,
+        implementation_id: uuid::Uuid,
+    ) -> sqlx::Result<Vec<Dependency>> {
+        sqlx::query_as(IMPLEMENTATION_DEPENDENCIES)
+            .bind(implementation_id)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn papers_by_dependency(
+        &self,
+        query: &DependencyQuery,
+    ) -> sqlx::Result<Vec<PaperSummary>> {
+        sqlx::query_as(&papers_by_dependency())
+            .bind(&query.name)
+            .bind(query.limit)
+            .bind(query.offset)
+            .bind(query.official)
+            .bind(query.include_deleted)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    async fn update_github_repo(&self, update: &GithubRepoUpdate) -> sqlx::Result<u64> {
+        let result = sqlx::query(UPDATE_GITHUB_REPO)
+            .bind(&update.full_name)
+            .bind(update.stars)
+            .bind(update.pushed_at)
+            .bind(update.archived)
+            .bind(update.disabled)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// SQLite has no `LISTEN`/`NOTIFY`.
+    async fn paper_events(&self) -> sqlx::Result<Option<BoxStream<'static, String>>> {
+        Ok(None)
+    }
+}
@@ -0,0 +1,167 @@
+//! Local cache for paper files (PDFs and thumbnails), so downloads survive
+//! dead arXiv mirrors.
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// Largest PDF the store will download, unless overridden with
+/// [`PaperStore::with_max_pdf_bytes`].
+pub const DEFAULT_MAX_PDF_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Where to download a paper's PDF from: its `pdf_url`, else arXiv.
+pub fn pdf_source_url(pdf_url: Option<String>, arxiv_id: Option<&str>) -> Option<String> {
+    pdf_url.or_else(|| arxiv_id.map(|arxiv_id| format!("https://arxiv.org/pdf/{}", arxiv_id)))
+}
+
+/// Paper files cached under a root directory (local disk or a mounted bucket).
+pub struct PaperStore {
+    root: PathBuf,
+    client: reqwest::Client,
+    max_pdf_bytes: u64,
+    /// Per-paper locks held while a PDF downloads, so concurrent requests for
+    /// the same paper wait for one download instead of starting their own
+    downloads: Mutex<HashMap<uuid::Uuid, Weak<tokio::sync::Mutex<()>>>>,
+}
+
+impl PaperStore {
+    /// Open the store at the given path, creating its directories if needed.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let root = path.as_ref().to_path_buf();
+
+        for dir in ["pdf", "thumbnail"] {
+            std::fs::create_dir_all(root.join(dir))
+                .with_context(|| format!("Failed to create paper store at {:?}", root))?;
+        }
+
+        let client = reqwest::Client::builder()
+            .user_agent("CodeWithPapers/1.0")
+            .timeout(Duration::from_secs(60))
+            .build()
+            .context("Failed to build HTTP client")?;
+
+        Ok(Self {
+            root,
+            client,
+            max_pdf_bytes: DEFAULT_MAX_PDF_BYTES,
+            downloads: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Refuse to download PDFs larger than `max_pdf_bytes`.
+    pub fn with_max_pdf_bytes(mut self, max_pdf_bytes: u64) -> Self {
+        self.max_pdf_bytes = max_pdf_bytes;
+        self
+    }
+
+    /// Path of a paper's cached PDF (which may not exist yet).
+    pub fn pdf_path(&self, paper_id: uuid::Uuid) -> PathBuf {
+        self.root.join("pdf").join(format!("{}.pdf", paper_id))
+    }
+
+    /// Path of a paper's first-page thumbnail (which may not exist yet).
+    pub fn thumbnail_path(&self, paper_id: uuid::Uuid) -> PathBuf {
+        self.root.join("thumbnail").join(format!("{}.png", paper_id))
+    }
+
+    /// Return the cached PDF for a paper, downloading it from `url` on a miss.
+    pub async fn fetch_pdf(&self, paper_id: uuid::Uuid, url: &str) -> Result<PathBuf> {
+        let path = self.pdf_path(paper_id);
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(path);
+        }
+
+        let lock = self.download_lock(paper_id);
+        let _guard = lock.lock().await;
+
+        // Another request may have finished the download while we waited
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(path);
+        }
+
+        // Stream to a temporary file first so readers never see a partial PDF
+        let tmp_path = path.with_extension(format!("{}.tmp", uuid::Uuid::new_v4()));
+        let result = async {
+            self.download_pdf(url, &tmp_path).await?;
+            tokio::fs::rename(&tmp_path, &path)
+                .await
+                .with_context(|| format!("Failed to move PDF into place at {:?}", path))
+        }
+        .await;
+        if result.is_err() {
+            tokio::fs::remove_file(&tmp_path).await.ok();
+        }
+        result.map(|()| path)
+    }
+
+    /// Lock for downloading one paper's PDF, shared by all callers until the
+    /// last of them drops it.
+    fn download_lock(&self, paper_id: uuid::Uuid) -> Arc<tokio::sync::Mutex<()>> {
+        let mut downloads = self.downloads.lock().unwrap();
+        downloads.retain(|_, lock| lock.strong_count() > 0);
+
+        if let Some(lock) = downloads.get(&paper_id).and_then(Weak::upgrade) {
+            return lock;
+        }
+        let lock = Arc::new(tokio::sync::Mutex::new(()));
+        downloads.insert(paper_id, Arc::downgrade(&lock));
+        lock
+    }
+
+    /// Download `url` to `dest`, failing if it is larger than `max_pdf_bytes`
+    /// or doesn't look like a PDF.
+    async fn download_pdf(&self, url: &str, dest: &Path) -> Result<()> {
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("Failed to download {}", url))?;
+
+        if response.content_length().is_some_and(|len| len > self.max_pdf_bytes) {
+            bail!("{} is larger than {} bytes", url, self.max_pdf_bytes);
+        }
+
+        let mut file = tokio::fs::File::create(dest)
+            .await
+            .with_context(|| format!("Failed to create {:?}", dest))?;
+        let mut header = Vec::with_capacity(5);
+        let mut written = 0u64;
+
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .with_context(|| format!("Failed to read {}", url))?
+        {
+            written += chunk.len() as u64;
+            if written > self.max_pdf_bytes {
+                bail!("{} is larger than {} bytes", url, self.max_pdf_bytes);
+            }
+
+            // Mirrors sometimes answer with an HTML error page and a 200
+            if header.len() < 5 {
+                let needed = (5 - header.len()).min(chunk.len());
+                header.extend_from_slice(&chunk[..needed]);
+                if header.len() == 5 && header != b"%PDF-" {
+                    bail!("{} did not return a PDF", url);
+                }
+            }
+
+            file.write_all(&chunk)
+                .await
+                .with_context(|| format!("Failed to write {:?}", dest))?;
+        }
+
+        if header != b"%PDF-" {
+            bail!("{} did not return a PDF", url);
+        }
+        file.flush()
+            .await
+            .with_context(|| format!("Failed to write {:?}", dest))?;
+        Ok(())
+    }
+}
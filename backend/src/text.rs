@@ -0,0 +1,180 @@
+//! Text normalization for paper metadata loaded from scraped sources.
+
+use regex::{Captures, Regex};
+use std::sync::LazyLock;
+
+/// Placeholders for LaTeX-escaped characters, so the escapes survive math and
+/// brace stripping (private-use code points never appear in real abstracts).
+const ESCAPED_DOLLAR: char = '\u{E000}';
+const ESCAPED_OPEN_BRACE: char = '\u{E001}';
+const ESCAPED_CLOSE_BRACE: char = '\u{E002}';
+
+static HTML_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"</?[A-Za-z][A-Za-z0-9]*(\s[^<>]*)?/?>").unwrap());
+static HTML_ENTITY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"&(#[0-9]+|#[xX][0-9A-Fa-f]+|[A-Za-z]+);").unwrap());
+static LATEX_DROPPED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\\(cite[pt]?|ref|eqref|label|footnote)\{[^{}]*\}").unwrap()
+});
+static LATEX_HREF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\\href\{[^{}]*\}\{([^{}]*)\}").unwrap());
+static LATEX_WRAPPER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"\\(textbf|textit|textsc|texttt|textrm|textsf|emph|underline|text|mbox|",
+        r"mathrm|mathbf|mathit|mathcal|mathbb|mathsf|mathtt|boldsymbol|url)\s*\{([^{}]*)\}"
+    ))
+    .unwrap()
+});
+static LATEX_COMMAND: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\\([A-Za-z]+)").unwrap());
+static LATEX_ESCAPE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\\([%&#_])").unwrap());
+static WHITESPACE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+").unwrap());
+
+fn latex_symbol(command: &str) -> Option<&'static str> {
+    Some(match command {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" | "varepsilon" => "ε",
+        "eta" => "η",
+        "theta" => "θ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "pi" => "π",
+        "rho" => "ρ",
+        "sigma" => "σ",
+        "tau" => "τ",
+        "phi" | "varphi" => "φ",
+        "omega" => "ω",
+        "Delta" => "Δ",
+        "Sigma" => "Σ",
+        "Omega" => "Ω",
+        "times" => "×",
+        "cdot" => "·",
+        "pm" => "±",
+        "le" | "leq" => "≤",
+        "ge" | "geq" => "≥",
+        "neq" => "≠",
+        "approx" => "≈",
+        "sim" => "~",
+        "infty" => "∞",
+        "to" | "rightarrow" => "→",
+        "ldots" | "dots" | "cdots" => "…",
+        // Commands that print their own name
+        "log" => "log",
+        "ln" => "ln",
+        "exp" => "exp",
+        "max" => "max",
+        "min" => "min",
+        "arg" => "arg",
+        "sin" => "sin",
+        "cos" => "cos",
+        "lim" => "lim",
+        "TeX" => "TeX",
+        "LaTeX" => "LaTeX",
+        _ => return None,
+    })
+}
+
+fn decode_entity(entity: &str) -> Option<String> {
+    let decoded = match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "ndash" => '–',
+        "mdash" => '—',
+        _ => {
+            let code = if let Some(hex) =
+                entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X"))
+            {
+                u32::from_str_radix(hex, 16).ok()?
+            } else {
+                entity.strip_prefix('#')?.parse().ok()?
+            };
+            char::from_u32(code)?
+        }
+    };
+    Some(decoded.to_string())
+}
+
+/// Turn a raw abstract into plain text: strip HTML tags, decode HTML
+/// entities, and unwrap LaTeX markup (`\textbf{x}` -> `x`, `$\alpha$` -> `α`).
+/// Unknown commands are dropped but their arguments are kept.
+pub fn clean_abstract(raw: &str) -> String {
+    let text = HTML_TAG.replace_all(raw, "");
+    let text = HTML_ENTITY.replace_all(&text, |caps: &Captures| {
+        decode_entity(&caps[1]).unwrap_or_else(|| caps[0].to_string())
+    });
+
+    let mut text = text
+        .replace(r"\\", " ")
+        .replace(r"\ ", " ")
+        .replace(r"\$", &ESCAPED_DOLLAR.to_string())
+        .replace(r"\{", &ESCAPED_OPEN_BRACE.to_string())
+        .replace(r"\}", &ESCAPED_CLOSE_BRACE.to_string());
+    text = LATEX_DROPPED.replace_all(&text, "").into_owned();
+    text = LATEX_HREF.replace_all(&text, "$1").into_owned();
+    // Unwrap from the inside out, since wrappers nest (\textbf{\emph{x}})
+    loop {
+        let unwrapped = LATEX_WRAPPER.replace_all(&text, "$2").into_owned();
+        if unwrapped == text {
+            break;
+        }
+        text = unwrapped;
+    }
+    text = LATEX_ESCAPE.replace_all(&text, "$1").into_owned();
+    // Other commands are dropped, keeping their arguments (\frac{a}{b} -> a b)
+    text = LATEX_COMMAND
+        .replace_all(&text, |caps: &Captures| latex_symbol(&caps[1]).unwrap_or(""))
+        .replace("}{", "} {");
+    let text: String = text
+        .replace("``", "\"")
+        .replace("''", "\"")
+        .replace(r"\(", "")
+        .replace(r"\)", "")
+        .replace(r"\[", "")
+        .replace(r"\]", "")
+        .chars()
+        .filter(|c| !matches!(c, '$' | '{' | '}'))
+        .map(|c| match c {
+            ESCAPED_DOLLAR => '$',
+            ESCAPED_OPEN_BRACE => '{',
+            ESCAPED_CLOSE_BRACE => '}',
+            c => c,
+        })
+        .collect();
+
+    WHITESPACE.replace_all(text.trim(), " ").into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abstracts_are_cleaned_to_plain_text() {
+        assert_eq!(
+            clean_abstract(r"We propose \textbf{BERT} with $O(n^2)$ cost &amp; $\alpha = 0.5$."),
+            "We propose BERT with O(n^2) cost & α = 0.5."
+        );
+        assert_eq!(
+            clean_abstract("<p>Accuracy &gt; 90&#37;, costs \\$5 \\cite{foo}</p>"),
+            "Accuracy > 90%, costs $5"
+        );
+    }
+
+    #[test]
+    fn unknown_latex_commands_keep_only_their_arguments() {
+        assert_eq!(
+            clean_abstract(r"A loss of $\frac{a}{b}$ with \sqrt{n} samples"),
+            "A loss of a b with n samples"
+        );
+        assert_eq!(
+            clean_abstract(r"Runs in $O(n \log n)$, see the \LaTeX\ source\newline{}"),
+            "Runs in O(n log n), see the LaTeX source"
+        );
+    }
+}
@@ -1,69 +1,1567 @@
-use backend::create_app;
-use sqlx::postgres::PgPoolOptions;
+use backend::{create_app, record_ingestion_run, AppConfig};
+use sqlx::postgres::{PgArguments, PgPool, PgPoolOptions};
+use sqlx::query::QueryScalar;
+use sqlx::{Connection, PgConnection, Postgres};
 use std::env;
+use std::path::PathBuf;
 use dotenvy::dotenv;
 use axum::{
-    body::Body,
+    body::{Body, Bytes},
     http::{Request, StatusCode},
+    response::Response,
+    Router,
 };
 use tower::ServiceExt; // for `oneshot`
 
+/// Connection to the test database plus the rows and scratch directories a
+/// test creates. Everything tracked is removed when it drops, so fixtures are
+/// cleaned up even when an assertion fails part-way through a test.
+struct TestDb {
+    pool: PgPool,
+    database_url: String,
+    rows: Vec<(&'static str, uuid::Uuid)>,
+    dirs: Vec<PathBuf>,
+}
+
+impl TestDb {
+    async fn connect() -> Self {
+        dotenv().ok();
+        let database_url = env::var("POSTGRES_URI").expect("POSTGRES_URI must be set");
+
+        let pool = PgPoolOptions::new()
+            .connect(&database_url)
+            .await
+            .expect("Failed to connect to database");
+
+        Self {
+            pool,
+            database_url,
+            rows: Vec::new(),
+            dirs: Vec::new(),
+        }
+    }
+
+    /// App with the default configuration and no search index or PDF store.
+    fn app(&self) -> Router {
+        self.app_with(AppConfig::default())
+    }
+
+    fn app_with(&self, config: AppConfig) -> Router {
+        create_app(self.pool.clone(), None, None, config)
+    }
+
+    /// Run an `INSERT ... RETURNING id` into `table`, deleting the row on drop.
+    async fn insert(
+        &mut self,
+        table: &'static str,
+        query: QueryScalar<'_, Postgres, uuid::Uuid, PgArguments>,
+    ) -> uuid::Uuid {
+        let id = query
+            .fetch_one(&self.pool)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to insert into {}: {}", table, e));
+        self.track(table, id)
+    }
+
+    /// Delete the row with `id` from `table` on drop.
+    fn track(&mut self, table: &'static str, id: uuid::Uuid) -> uuid::Uuid {
+        self.rows.push((table, id));
+        id
+    }
+
+    /// Path for a scratch directory that is removed on drop.
+    fn temp_dir(&mut self, prefix: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("{}-{}", prefix, uuid::Uuid::new_v4().simple()));
+        self.dirs.push(dir.clone());
+        dir
+    }
+}
+
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        for dir in &self.dirs {
+            std::fs::remove_dir_all(dir).ok();
+        }
+
+        let rows = std::mem::take(&mut self.rows);
+        if rows.is_empty() {
+            return;
+        }
+
+        // The test's runtime may be unwinding, so clean up on a fresh one
+        let database_url = self.database_url.clone();
+        let cleanup = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("Failed to start cleanup runtime");
+            runtime.block_on(async {
+                let mut conn = PgConnection::connect(&database_url).await?;
+                for (table, id) in rows.iter().rev() {
+                    sqlx::query(&format!("DELETE FROM {} WHERE id = $1", table))
+                        .bind(id)
+                        .execute(&mut conn)
+                        .await?;
+                }
+                Ok::<_, sqlx::Error>(())
+            })
+        });
+
+        match cleanup.join() {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => eprintln!("Failed to clean up test fixtures: {}", e),
+            Err(_) => eprintln!("Failed to clean up test fixtures"),
+        }
+    }
+}
+
+async fn send(app: Router, request: Request<Body>) -> Response {
+    app.oneshot(request).await.unwrap()
+}
+
+async fn get(app: Router, uri: &str) -> Response {
+    send(app, Request::builder().uri(uri).body(Body::empty()).unwrap()).await
+}
+
+async fn body_bytes(response: Response) -> Bytes {
+    axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap()
+}
+
+/// Status and JSON body of a response; the body is `Null` if it isn't JSON.
+async fn into_json(response: Response) -> (StatusCode, serde_json::Value) {
+    let status = response.status();
+    let body = body_bytes(response).await;
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+async fn get_json(app: Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    into_json(get(app, uri).await).await
+}
+
 #[tokio::test]
 async fn health_check_works() {
-    dotenv().ok();
-    let database_url = env::var("POSTGRES_URI").expect("POSTGRES_URI must be set");
-    
-    let pool = PgPoolOptions::new()
-        .connect(&database_url)
+    let db = TestDb::connect().await;
+
+    let response = get(db.app(), "/api/health").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn can_fetch_papers() {
+    let db = TestDb::connect().await;
+
+    // Check if tables exist by trying to query
+    let row: (i64,) = sqlx::query_as("SELECT count(*) FROM papers")
+        .fetch_one(&db.pool)
+        .await
+        .expect("Failed to query papers table. Does it exist?");
+
+    println!("Found {} papers", row.0);
+
+    let (status, json) = get_json(db.app(), "/api/papers").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(json["papers"].is_array());
+}
+
+#[tokio::test]
+async fn can_filter_reproducible_implementations() {
+    let mut db = TestDb::connect().await;
+
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title) VALUES ('Reproducible filter test paper') RETURNING id",
+            ),
+        )
+        .await;
+
+    // Enough stars to sort ahead of every other implementation
+    let mut ids = Vec::new();
+    for (github_url, has_dockerfile) in [
+        ("https://github.com/cwp-test/reproducible", Some(true)),
+        ("https://github.com/cwp-test/not-reproducible", Some(false)),
+    ] {
+        let (id,): (uuid::Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO implementations (paper_id, github_url, stars, has_dockerfile)
+            VALUES ($1, $2, 2000000000, $3)
+            RETURNING id
+            "#,
+        )
+        .bind(paper_id)
+        .bind(github_url)
+        .bind(has_dockerfile)
+        .fetch_one(&db.pool)
+        .await
+        .expect("Failed to insert implementation");
+        ids.push(id.to_string());
+    }
+
+    let listed_ids = |reproducible: bool| {
+        let app = db.app();
+        let uri = format!("/api/implementations?reproducible={}&limit=100", reproducible);
+        async move {
+            let (status, json) = get_json(app, &uri).await;
+            assert_eq!(status, StatusCode::OK);
+            json.as_array()
+                .unwrap()
+                .iter()
+                .map(|implementation| implementation["id"].as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        }
+    };
+
+    let reproducible = listed_ids(true).await;
+    assert!(reproducible.contains(&ids[0]));
+    assert!(!reproducible.contains(&ids[1]));
+
+    let not_reproducible = listed_ids(false).await;
+    assert!(!not_reproducible.contains(&ids[0]));
+    assert!(not_reproducible.contains(&ids[1]));
+}
+
+#[tokio::test]
+async fn implementation_includes_packages() {
+    let mut db = TestDb::connect().await;
+
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title) VALUES ('Package test paper') RETURNING id",
+            ),
+        )
+        .await;
+
+    let (impl_id,): (uuid::Uuid,) = sqlx::query_as(
+        "INSERT INTO implementations (paper_id, github_url) VALUES ($1, 'https://github.com/example/pkg') RETURNING id",
+    )
+    .bind(paper_id)
+    .fetch_one(&db.pool)
+    .await
+    .expect("Failed to insert implementation");
+
+    sqlx::query(
+        "INSERT INTO packages (implementation_id, registry, name, url) VALUES ($1, 'pypi', 'example-pkg', 'https://pypi.org/project/example-pkg/')",
+    )
+    .bind(impl_id)
+    .execute(&db.pool)
+    .await
+    .expect("Failed to insert package");
+
+    let (status, json) = get_json(db.app(), &format!("/api/implementations/{}", impl_id)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["packages"][0]["install_command"], "pip install example-pkg");
+}
+
+/// Add a benchmark on a new dataset called `dataset`, with one paper per
+/// `(arxiv_id, value)` reporting `metric` on it.
+async fn insert_leaderboard(
+    db: &mut TestDb,
+    dataset: &str,
+    metric: &str,
+    results: &[(&str, f64)],
+) {
+    let dataset_id = db
+        .insert(
+            "datasets",
+            sqlx::query_scalar("INSERT INTO datasets (name) VALUES ($1) RETURNING id")
+                .bind(dataset),
+        )
+        .await;
+
+    let benchmark_id = db
+        .insert(
+            "benchmarks",
+            sqlx::query_scalar(
+                "INSERT INTO benchmarks (name, dataset_id, task) VALUES ($1, $2, 'Badge test') RETURNING id",
+            )
+            .bind(format!("{} - Badge test", dataset))
+            .bind(dataset_id),
+        )
+        .await;
+
+    for (arxiv_id, value) in results {
+        let paper_id = db
+            .insert(
+                "papers",
+                sqlx::query_scalar(
+                    "INSERT INTO papers (title, arxiv_id) VALUES ('Badge test paper', $1) RETURNING id",
+                )
+                .bind(arxiv_id),
+            )
+            .await;
+
+        sqlx::query(
+            r#"
+            INSERT INTO benchmark_results (paper_id, benchmark_id, metric_name, metric_value)
+            VALUES ($1, $2, $3, $4::numeric)
+            "#,
+        )
+        .bind(paper_id)
+        .bind(benchmark_id)
+        .bind(metric)
+        .bind(value)
+        .execute(&db.pool)
         .await
-        .expect("Failed to connect to database");
+        .expect("Failed to insert result");
+    }
+}
+
+#[tokio::test]
+async fn paper_badge_reports_sota_result() {
+    let mut db = TestDb::connect().await;
+
+    // A lone result isn't state of the art
+    insert_leaderboard(&mut db, "Badge Test VOC", "mAP", &[("9901.00001", 84.2)]).await;
+
+    let (status, json) = get_json(db.app(), "/api/badge/paper/9901.00001").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["schemaVersion"], 1);
+    assert_eq!(json["label"], "CodeWithPapers");
+
+    insert_leaderboard(
+        &mut db,
+        "Badge Test COCO",
+        "mAP",
+        &[("9901.00002", 84.2), ("9901.00003", 80.0)],
+    )
+    .await;
+
+    let (status, json) = get_json(db.app(), "/api/badge/paper/9901.00002").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["label"], "SOTA");
+    assert_eq!(json["message"], "84.2 mAP on Badge Test COCO");
+
+    let (_, json) = get_json(db.app(), "/api/badge/paper/9901.00003").await;
+
+    assert_eq!(json["label"], "CodeWithPapers");
+}
+
+#[tokio::test]
+async fn paper_badge_ranks_lower_is_better_metrics() {
+    let mut db = TestDb::connect().await;
+
+    // FID is registered as lower-is-better in the metrics table
+    insert_leaderboard(
+        &mut db,
+        "Badge Test FFHQ",
+        "FID",
+        &[("9901.00004", 3.1), ("9901.00005", 5.4)],
+    )
+    .await;
+
+    let (status, json) = get_json(db.app(), "/api/badge/paper/9901.00004").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["label"], "SOTA");
+    assert_eq!(json["message"], "3.1 FID on Badge Test FFHQ");
+
+    let (_, json) = get_json(db.app(), "/api/badge/paper/9901.00005").await;
+
+    assert_eq!(json["label"], "CodeWithPapers");
+}
+
+#[tokio::test]
+async fn paper_aggregates_implementation_languages() {
+    let mut db = TestDb::connect().await;
+
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title) VALUES ('Language test paper') RETURNING id",
+            ),
+        )
+        .await;
+
+    sqlx::query(
+        r#"
+        INSERT INTO implementations (paper_id, github_url, languages) VALUES
+            ($1, 'https://github.com/example/a', '{"Python": 600, "Cuda": 100}'),
+            ($1, 'https://github.com/example/b', '{"Python": 300}')
+        "#,
+    )
+    .bind(paper_id)
+    .execute(&db.pool)
+    .await
+    .expect("Failed to insert implementations");
+
+    let (status, json) = get_json(db.app(), &format!("/api/papers/{}", paper_id)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["languages"][0]["language"], "Python");
+    assert_eq!(json["languages"][0]["bytes"], 900);
+    assert_eq!(json["languages"][0]["percentage"], 90.0);
+}
+
+#[tokio::test]
+async fn can_find_papers_by_dependency() {
+    let mut db = TestDb::connect().await;
+
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title) VALUES ('Dependency test paper') RETURNING id",
+            ),
+        )
+        .await;
+
+    let (impl_id,): (uuid::Uuid,) = sqlx::query_as(
+        "INSERT INTO implementations (paper_id, github_url, is_official) VALUES ($1, 'https://github.com/example/dep', TRUE) RETURNING id",
+    )
+    .bind(paper_id)
+    .fetch_one(&db.pool)
+    .await
+    .expect("Failed to insert implementation");
+
+    sqlx::query(
+        "INSERT INTO implementation_dependencies (implementation_id, name, version_spec, source) VALUES ($1, 'dep-test-diffusers', '>=0.20', 'requirements.txt')",
+    )
+    .bind(impl_id)
+    .execute(&db.pool)
+    .await
+    .expect("Failed to insert dependency");
+
+    // Lookup is PEP 503 normalized, so differently-cased names still match
+    let (status, json) =
+        get_json(db.app(), "/api/dependencies/Dep_Test.Diffusers/papers?official=true").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json[0]["id"], paper_id.to_string());
+}
+
+#[tokio::test]
+async fn archived_implementations_are_demoted() {
+    let mut db = TestDb::connect().await;
+
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title) VALUES ('Archived test paper') RETURNING id",
+            ),
+        )
+        .await;
+
+    sqlx::query(
+        "INSERT INTO implementations (paper_id, github_url, stars, archived) VALUES ($1, 'https://github.com/example/old', 10, TRUE), ($1, 'https://github.com/example/new', 10, FALSE)",
+    )
+    .bind(paper_id)
+    .execute(&db.pool)
+    .await
+    .expect("Failed to insert implementations");
+
+    // Equally starred, so only the archived flag separates them
+    let (status, json) = get_json(db.app(), &format!("/api/papers/{}", paper_id)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["implementations"][0]["github_url"], "https://github.com/example/new");
+    assert_eq!(json["implementations"][1]["archived"], true);
+
+    let (status, json) = get_json(db.app(), "/api/implementations?archived=true&limit=100").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let implementations = json.as_array().unwrap();
+    assert!(implementations.iter().all(|i| i["archived"] == true));
+    assert!(implementations
+        .iter()
+        .any(|i| i["github_url"] == "https://github.com/example/old"));
+}
+
+#[tokio::test]
+async fn paper_implementations_are_ranked() {
+    let mut db = TestDb::connect().await;
+
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title) VALUES ('Ranking test paper') RETURNING id",
+            ),
+        )
+        .await;
+
+    // Stars outrank maintenance; archival only breaks ties between equal stars
+    sqlx::query(
+        r#"
+        INSERT INTO implementations (paper_id, github_url, stars, is_official, archived) VALUES
+            ($1, 'https://github.com/example/archived', 900, FALSE, TRUE),
+            ($1, 'https://github.com/example/popular', 300, FALSE, FALSE),
+            ($1, 'https://github.com/example/official', 5, TRUE, FALSE),
+            ($1, 'https://github.com/example/stale', 300, FALSE, TRUE)
+        "#,
+    )
+    .bind(paper_id)
+    .execute(&db.pool)
+    .await
+    .expect("Failed to insert implementations");
+
+    let (status, json) =
+        get_json(db.app(), &format!("/api/papers/{}/implementations", paper_id)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let urls: Vec<&str> = json
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["github_url"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        urls,
+        [
+            "https://github.com/example/official",
+            "https://github.com/example/archived",
+            "https://github.com/example/popular",
+            "https://github.com/example/stale",
+        ]
+    );
+    assert_eq!(json[0]["rank"], 1);
+    assert_eq!(json[3]["rank"], 4);
+}
 
-    let app = create_app(pool);
+#[tokio::test]
+async fn paper_pdf_is_served_from_store_with_ranges() {
+    let mut db = TestDb::connect().await;
+
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title, pdf_url) VALUES ('PDF test paper', 'https://example.invalid/paper.pdf') RETURNING id",
+            ),
+        )
+        .await;
+
+    let store_dir = db.temp_dir("cwp-paper-store");
+    let store = backend::storage::PaperStore::open(&store_dir).expect("Failed to open store");
+    std::fs::write(store.pdf_path(paper_id), b"%PDF-1.4 test").unwrap();
+
+    let app = create_app(
+        db.pool.clone(),
+        None,
+        Some(std::sync::Arc::new(store)),
+        AppConfig::default(),
+    );
+
+    let response = send(
+        app,
+        Request::builder()
+            .uri(format!("/api/papers/{}/pdf", paper_id))
+            .header("range", "bytes=0-4")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()["content-type"], "application/pdf");
+    assert_eq!(&body_bytes(response).await[..], b"%PDF-");
+
+    // Without a store the endpoint redirects to the upstream PDF
+    let response = get(db.app(), &format!("/api/papers/{}/pdf", paper_id)).await;
+
+    assert_eq!(response.status(), StatusCode::TEMPORARY_REDIRECT);
+    assert_eq!(response.headers()["location"], "https://example.invalid/paper.pdf");
+}
+
+#[tokio::test]
+async fn paper_store_downloads_each_pdf_once() {
+    use backend::storage::PaperStore;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let mut db = TestDb::connect().await;
+
+    let downloads = Arc::new(AtomicUsize::new(0));
+    let counter = downloads.clone();
+    let upstream = Router::new()
+        .route(
+            "/paper.pdf",
+            axum::routing::get(move || async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                b"%PDF-1.4 upstream".to_vec()
+            }),
+        )
+        .route("/large.pdf", axum::routing::get(|| async { format!("%PDF-{}", " ".repeat(4096)) }))
+        .route("/missing.pdf", axum::routing::get(|| async { "<html>Not found</html>" }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let upstream_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, upstream).await.unwrap() });
+
+    let store_dir = db.temp_dir("cwp-paper-store");
+    let store = PaperStore::open(&store_dir)
+        .expect("Failed to open store")
+        .with_max_pdf_bytes(1024);
+
+    // Concurrent requests for one paper share a single download
+    let paper_id = uuid::Uuid::new_v4();
+    let url = format!("{}/paper.pdf", upstream_url);
+    let fetches = (0..4).map(|_| store.fetch_pdf(paper_id, &url));
+    for path in futures::future::join_all(fetches).await {
+        assert_eq!(path.unwrap(), store.pdf_path(paper_id));
+    }
+    assert_eq!(downloads.load(Ordering::SeqCst), 1);
+    assert_eq!(std::fs::read(store.pdf_path(paper_id)).unwrap(), b"%PDF-1.4 upstream");
+
+    // Oversized files and error pages leave nothing behind
+    for (path, error) in [("large.pdf", "larger than"), ("missing.pdf", "did not return a PDF")] {
+        let paper_id = uuid::Uuid::new_v4();
+        let url = format!("{}/{}", upstream_url, path);
+        let error_message = store.fetch_pdf(paper_id, &url).await.unwrap_err().to_string();
+        assert!(error_message.contains(error), "{}", error_message);
+    }
+    assert_eq!(std::fs::read_dir(store_dir.join("pdf")).unwrap().count(), 1);
+}
+
+#[tokio::test]
+async fn paper_thumbnail_is_served_from_store() {
+    let mut db = TestDb::connect().await;
 
-    let response = app
-        .oneshot(
-            Request::builder()
-                .uri("/api/health")
-                .body(Body::empty())
-                .unwrap(),
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title) VALUES ('Thumbnail test paper') RETURNING id",
+            ),
         )
+        .await;
+    let store_dir = db.temp_dir("cwp-paper-store");
+    let store = backend::storage::PaperStore::open(&store_dir).expect("Failed to open store");
+    let store = std::sync::Arc::new(store);
+
+    let config = AppConfig {
+        admin_token: Some("test-admin-token".to_string()),
+        ..Default::default()
+    };
+    let app = || create_app(db.pool.clone(), None, Some(store.clone()), config.clone());
+    let uri = format!("/api/papers/{}/thumbnail", paper_id);
+
+    let response = get(app(), &uri).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    std::fs::write(store.thumbnail_path(paper_id), b"\x89PNG\r\n\x1a\n").unwrap();
+
+    let response = get(app(), &uri).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/png");
+
+    // A thumbnail left behind by a retracted paper isn't served
+    sqlx::query("UPDATE papers SET deleted_at = NOW() WHERE id = $1")
+        .bind(paper_id)
+        .execute(&db.pool)
         .await
+        .expect("Failed to soft-delete paper");
+
+    let response = get(app(), &uri).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let request = Request::builder()
+        .uri(format!("{}?include_deleted=true", uri))
+        .header("Authorization", "Bearer test-admin-token")
+        .body(Body::empty())
         .unwrap();
+    let response = send(app(), request).await;
 
     assert_eq!(response.status(), StatusCode::OK);
+
+    let response = get(app(), &format!("/api/papers/{}/thumbnail", uuid::Uuid::new_v4())).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
 }
 
 #[tokio::test]
-async fn can_fetch_papers() {
-    dotenv().ok();
-    let database_url = env::var("POSTGRES_URI").expect("POSTGRES_URI must be set");
+async fn paper_includes_requested_translation() {
+    let mut db = TestDb::connect().await;
+
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title, abstract) VALUES ('Translation test paper', 'An abstract.') RETURNING id",
+            ),
+        )
+        .await;
 
-    let pool = PgPoolOptions::new()
-        .connect(&database_url)
+    sqlx::query(
+        "INSERT INTO paper_translations (paper_id, language, title, abstract, provider) VALUES ($1, 'de', 'Testpapier', 'Eine Zusammenfassung.', 'deepl')",
+    )
+    .bind(paper_id)
+    .execute(&db.pool)
+    .await
+    .expect("Failed to insert translation");
+
+    let (status, json) = get_json(db.app(), &format!("/api/papers/{}?lang=DE", paper_id)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["abstract"], "An abstract.");
+    assert_eq!(json["translation"]["language"], "de");
+    assert_eq!(json["translation"]["abstract"], "Eine Zusammenfassung.");
+
+    let (_, json) = get_json(db.app(), &format!("/api/papers/{}", paper_id)).await;
+
+    assert!(json.get("translation").is_none());
+}
+
+#[tokio::test]
+async fn arxiv_lookup_returns_latest_version() {
+    let mut db = TestDb::connect().await;
+
+    // Insert v2 before v1 so the latest version is not the newest row
+    let base_id = format!("cwp.{}", &uuid::Uuid::new_v4().simple().to_string()[..10]);
+    let mut paper_ids = Vec::new();
+    for version in [2, 1] {
+        let paper_id = db
+            .insert(
+                "papers",
+                sqlx::query_scalar(
+                    "INSERT INTO papers (title, arxiv_id) VALUES ('Versioned test paper', $1) RETURNING id",
+                )
+                .bind(format!("{}v{}", base_id, version)),
+            )
+            .await;
+        paper_ids.push(paper_id);
+    }
+
+    // Asking for an older version still resolves to the latest one
+    let (status, json) = get_json(db.app(), &format!("/api/papers/by-arxiv/{}v1", base_id)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["id"], paper_ids[0].to_string());
+    assert_eq!(json["arxiv_id"], format!("{}v2", base_id));
+
+    let versions = json["versions"].as_array().unwrap();
+    assert_eq!(versions.len(), 2);
+    assert_eq!(versions[0]["version"], 2);
+    assert_eq!(versions[1]["id"], paper_ids[1].to_string());
+
+    // Search lists the paper once, as its latest version
+    let (_, json) = get_json(db.app(), "/api/papers?q=Versioned%20test%20paper").await;
+
+    let matches: Vec<&serde_json::Value> = json["papers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|p| p["versions"][0]["arxiv_id"].as_str() == Some(&format!("{}v2", base_id)))
+        .collect();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0]["id"], paper_ids[0].to_string());
+}
+
+#[tokio::test]
+async fn arxiv_versions_share_code_and_orcids() {
+    let mut db = TestDb::connect().await;
+
+    // v1 was loaded with an arXiv: prefix and has all the linked data
+    let base_id = format!("cwp.{}", &uuid::Uuid::new_v4().simple().to_string()[..8]);
+    let v1 = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title, arxiv_id) VALUES ('Linked version test paper', $1) RETURNING id",
+            )
+            .bind(format!("arXiv:{}v1", base_id)),
+        )
+        .await;
+
+    let repo = format!("https://github.com/cwp-test/versions-{}", uuid::Uuid::new_v4().simple());
+    sqlx::query(
+        r#"
+        INSERT INTO implementations (paper_id, github_url, stars, languages) VALUES
+            ($1, $2, 10, '{"Python": 300}'),
+            ($1, $3, 5, '{"Rust": 100}')
+        "#,
+    )
+    .bind(v1)
+    .bind(&repo)
+    .bind(format!("{}-extra", repo))
+    .execute(&db.pool)
+    .await
+    .expect("Failed to insert implementations");
+
+    sqlx::query(
+        "INSERT INTO paper_authors (paper_id, position, name, orcid, source) VALUES ($1, 0, 'Versioned Author', '0000-0002-1694-233X', 'test')",
+    )
+    .bind(v1)
+    .execute(&db.pool)
+    .await
+    .expect("Failed to insert paper author");
+
+    let dependency = format!("cwp-test-versions-{}", uuid::Uuid::new_v4().simple());
+    sqlx::query(
+        r#"
+        INSERT INTO implementation_dependencies (implementation_id, name, source)
+        SELECT id, $2, 'requirements.txt' FROM implementations WHERE paper_id = $1
+        "#,
+    )
+    .bind(v1)
+    .bind(&dependency)
+    .execute(&db.pool)
+    .await
+    .expect("Failed to insert dependencies");
+
+    // v2 arrives later, and one repository is linked to it as well
+    let v2 = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title, arxiv_id) VALUES ('Linked version test paper', $1) RETURNING id",
+            )
+            .bind(format!("{}v2", base_id)),
+        )
+        .await;
+
+    sqlx::query(
+        r#"INSERT INTO implementations (paper_id, github_url, stars, languages) VALUES ($1, $2, 10, '{"Python": 300}')"#,
+    )
+    .bind(v2)
+    .bind(&repo)
+    .execute(&db.pool)
+    .await
+    .expect("Failed to insert implementation");
+
+    let (status, json) = get_json(db.app(), &format!("/api/papers/by-arxiv/{}", base_id)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["id"], v2.to_string());
+    assert_eq!(json["has_code"], true);
+    assert_eq!(json["versions"].as_array().unwrap().len(), 2);
+
+    let implementations = json["implementations"].as_array().unwrap();
+    assert_eq!(implementations.len(), 2);
+    assert_eq!(implementations[0]["paper_id"], v2.to_string());
+    assert_eq!(implementations[1]["paper_id"], v1.to_string());
+
+    let languages: Vec<&str> = json["languages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| l["language"].as_str().unwrap())
+        .collect();
+    assert_eq!(languages, ["Python", "Rust"]);
+
+    assert_eq!(json["author_orcids"][0]["orcid"], "0000-0002-1694-233X");
+
+    let (_, json) = get_json(db.app(), &format!("/api/papers/{}/implementations", v2)).await;
+
+    assert_eq!(json.as_array().unwrap().len(), 2);
+
+    // Author and dependency lookups list the paper once, as v2, although
+    // the ORCID and dependencies were recorded on v1
+    let (status, json) = get_json(db.app(), "/api/authors/orcid/0000-0002-1694-233X").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["papers"], serde_json::json!([{
+        "id": v2.to_string(),
+        "title": "Linked version test paper",
+        "arxiv_id": format!("{}v2", base_id),
+        "published_date": null,
+    }]));
+
+    let uri = format!("/api/dependencies/{}/papers", dependency);
+    let (status, json) = get_json(db.app(), &uri).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json.as_array().unwrap().len(), 1);
+    assert_eq!(json[0]["id"], v2.to_string());
+}
+
+#[tokio::test]
+async fn can_fetch_paper_by_doi() {
+    let mut db = TestDb::connect().await;
+
+    let doi = format!("10.5555/cwp-test.{}", uuid::Uuid::new_v4().simple());
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title, doi) VALUES ('Journal-only test paper', $1) RETURNING id",
+            )
+            .bind(&doi),
+        )
+        .await;
+
+    // Resolver-style prefixes and case differences resolve to the same paper
+    let uri = format!("/api/papers/by-doi/https://doi.org/{}", doi.to_uppercase());
+    let (status, json) = get_json(db.app(), &uri).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["id"], paper_id.to_string());
+    assert_eq!(json["doi"], doi);
+
+    let response = get(db.app(), "/api/papers/by-doi/not-a-doi").await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn can_find_author_papers_by_orcid() {
+    let mut db = TestDb::connect().await;
+
+    let mut paper_ids = Vec::new();
+    for published_date in ["2023-05-02", "2023-05-01"] {
+        let paper_id = db
+            .insert(
+                "papers",
+                sqlx::query_scalar(
+                    r#"
+                    INSERT INTO papers (title, authors, published_date)
+                    VALUES ('ORCID test paper', '["Josiah Carberry"]', $1::date)
+                    RETURNING id
+                    "#,
+                )
+                .bind(published_date),
+            )
+            .await;
+
+        sqlx::query(
+            "INSERT INTO paper_authors (paper_id, position, name, orcid, source) VALUES ($1, 0, 'Josiah Carberry', '0000-0002-1825-0097', 'test')",
+        )
+        .bind(paper_id)
+        .execute(&db.pool)
         .await
-        .expect("Failed to connect to database");
+        .expect("Failed to insert paper author");
+        paper_ids.push(paper_id.to_string());
+    }
 
-    // Check if tables exist by trying to query
-    let row: (i64,) = sqlx::query_as("SELECT count(*) FROM papers")
-        .fetch_one(&pool)
+    let (status, json) = get_json(db.app(), "/api/authors/orcid/0000-0002-1825-0097").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["name"], "Josiah Carberry");
+    let ids: Vec<&str> = json["papers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, paper_ids);
+
+    // Papers are paginated, newest first
+    let pages = [(0, Some(paper_ids[0].as_str())), (1, Some(paper_ids[1].as_str())), (2, None)];
+    for (offset, expected) in pages {
+        let uri = format!("/api/authors/orcid/0000-0002-1825-0097?limit=1&offset={}", offset);
+        let (status, json) = get_json(db.app(), &uri).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let papers = json["papers"].as_array().unwrap();
+        assert!(papers.len() <= 1);
+        assert_eq!(papers.first().and_then(|p| p["id"].as_str()), expected);
+    }
+
+    let response = get(db.app(), "/api/authors/orcid/0000-0002-1825-0098").await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn latest_papers_are_ordered_by_publication_time() {
+    let mut db = TestDb::connect().await;
+
+    // Same published_date, so only published_at tells them apart
+    let title = format!("Timestamp test {}", uuid::Uuid::new_v4().simple());
+    let mut paper_ids = Vec::new();
+    for published_at in ["2023-01-02T18:00:00Z", "2023-01-02T09:00:00Z"] {
+        let paper_id = db
+            .insert(
+                "papers",
+                sqlx::query_scalar(
+                    r#"
+                    INSERT INTO papers (title, published_date, published_at)
+                    VALUES ($1, '2023-01-02', $2::timestamptz)
+                    RETURNING id
+                    "#,
+                )
+                .bind(&title)
+                .bind(published_at),
+            )
+            .await;
+        paper_ids.push(paper_id.to_string());
+    }
+
+    let oldest_first: Vec<String> = paper_ids.iter().rev().cloned().collect();
+    for (order, expected) in [("desc", paper_ids.clone()), ("asc", oldest_first)] {
+        let uri = format!("/api/papers?q={}&order={}", title.replace(' ', "%20"), order);
+        let (_, json) = get_json(db.app(), &uri).await;
+
+        let ids: Vec<String> = json["papers"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(ids, expected);
+    }
+}
+
+#[tokio::test]
+async fn soft_deleted_papers_are_hidden_except_from_admins() {
+    let mut db = TestDb::connect().await;
+
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                r#"
+                INSERT INTO papers (title, deleted_at, deletion_reason)
+                VALUES ('Retracted test paper', NOW(), 'retracted')
+                RETURNING id
+                "#,
+            ),
+        )
+        .await;
+
+    let config = AppConfig {
+        admin_token: Some("test-admin-token".to_string()),
+        ..Default::default()
+    };
+    let cases = [
+        ("", None, StatusCode::NOT_FOUND),
+        ("?include_deleted=true", None, StatusCode::FORBIDDEN),
+        ("?include_deleted=true", Some("Bearer wrong-token"), StatusCode::FORBIDDEN),
+        ("?include_deleted=true", Some("Bearer test-admin-token"), StatusCode::OK),
+    ];
+
+    for (query, authorization, expected) in cases {
+        let mut request = Request::builder().uri(format!("/api/papers/{}{}", paper_id, query));
+        if let Some(authorization) = authorization {
+            request = request.header("Authorization", authorization);
+        }
+        let request = request.body(Body::empty()).unwrap();
+        let (status, json) = into_json(send(db.app_with(config.clone()), request).await).await;
+
+        assert_eq!(status, expected, "{} {:?}", query, authorization);
+
+        if expected == StatusCode::OK {
+            assert!(json["deleted_at"].is_string());
+        }
+    }
+}
+
+#[tokio::test]
+async fn updated_at_is_maintained_by_the_database() {
+    let mut db = TestDb::connect().await;
+
+    let (paper_id, inserted_at): (uuid::Uuid, chrono::DateTime<chrono::Utc>) = sqlx::query_as(
+        r#"
+        INSERT INTO papers (title, updated_at)
+        VALUES ('Timestamp trigger test paper', NULL)
+        RETURNING id, updated_at
+        "#,
+    )
+    .fetch_one(&db.pool)
+    .await
+    .expect("Failed to insert paper");
+    db.track("papers", paper_id);
+
+    // Writers no longer set updated_at themselves
+    let (updated_at,): (chrono::DateTime<chrono::Utc>,) = sqlx::query_as(
+        "UPDATE papers SET title = 'Renamed trigger test paper' WHERE id = $1 RETURNING updated_at",
+    )
+    .bind(paper_id)
+    .fetch_one(&db.pool)
+    .await
+    .expect("Failed to update paper");
+
+    assert!(updated_at > inserted_at);
+}
+
+#[tokio::test]
+async fn github_webhook_updates_repository_metadata() {
+    use hmac::{Hmac, Mac};
+
+    let mut db = TestDb::connect().await;
+
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar(
+                "INSERT INTO papers (title) VALUES ('Webhook test paper') RETURNING id",
+            ),
+        )
+        .await;
+
+    // Stored URL differs in case and suffix from the webhook's full_name
+    let repo = format!("cwp-test/repo-{}", uuid::Uuid::new_v4().simple());
+    sqlx::query("INSERT INTO implementations (paper_id, github_url, stars) VALUES ($1, $2, 5)")
+        .bind(paper_id)
+        .bind(format!("https://github.com/{}.git", repo.to_uppercase()))
+        .execute(&db.pool)
         .await
-        .expect("Failed to query papers table. Does it exist?");
+        .expect("Failed to insert implementation");
 
-    println!("Found {} papers", row.0);
+    let body = serde_json::json!({
+        "action": "created",
+        "repository": {
+            "full_name": repo,
+            "stargazers_count": 42,
+            "pushed_at": "2024-05-01T12:00:00Z",
+            "archived": true
+        }
+    })
+    .to_string();
+
+    let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"test-webhook-secret").unwrap();
+    mac.update(body.as_bytes());
+    let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+
+    let config = AppConfig {
+        github_webhook_secret: Some("test-webhook-secret".to_string()),
+        ..Default::default()
+    };
 
-    let app = create_app(pool);
+    let cases = [
+        (config.clone(), "sha256=00", StatusCode::UNAUTHORIZED),
+        (AppConfig::default(), signature.as_str(), StatusCode::SERVICE_UNAVAILABLE),
+        (config.clone(), signature.as_str(), StatusCode::OK),
+    ];
 
-    let response = app
-        .oneshot(
-            Request::builder()
-                .uri("/api/papers")
-                .body(Body::empty())
-                .unwrap(),
+    for (config, signature, expected) in cases {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/webhooks/github")
+            .header("X-GitHub-Event", "star")
+            .header("X-Hub-Signature-256", signature)
+            .body(Body::from(body.clone()))
+            .unwrap();
+        let response = send(db.app_with(config), request).await;
+
+        assert_eq!(response.status(), expected);
+    }
+
+    let (stars, archived, pushed_at): (i32, bool, chrono::DateTime<chrono::Utc>) =
+        sqlx::query_as("SELECT stars, archived, pushed_at FROM implementations WHERE paper_id = $1")
+            .bind(paper_id)
+            .fetch_one(&db.pool)
+            .await
+            .expect("Failed to fetch implementation");
+
+    assert_eq!(stars, 42);
+    assert!(archived);
+    assert_eq!(pushed_at.to_rfc3339(), "2024-05-01T12:00:00+00:00");
+}
+
+#[tokio::test]
+async fn paper_events_are_streamed() {
+    use backend::{PaperEvent, PAPER_EVENTS_CHANNEL};
+    use futures::StreamExt;
+
+    let db = TestDb::connect().await;
+
+    let response = get(db.app(), "/api/papers/stream").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+    let event = PaperEvent {
+        id: uuid::Uuid::new_v4(),
+        title: "Streamed Paper".to_string(),
+        arxiv_id: Some("2401.99999".to_string()),
+        published_at: None,
+        categories: vec!["cs.LG".to_string()],
+    };
+    sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(PAPER_EVENTS_CHANNEL)
+        .bind(serde_json::to_string(&event).unwrap())
+        .execute(&db.pool)
+        .await
+        .expect("Failed to notify");
+
+    let mut body = response.into_body().into_data_stream();
+    let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), body.next())
+        .await
+        .expect("Timed out waiting for event")
+        .unwrap()
+        .unwrap();
+    let chunk = String::from_utf8(chunk.to_vec()).unwrap();
+
+    assert!(chunk.starts_with("event: paper\n"), "{}", chunk);
+    let data = chunk
+        .lines()
+        .find_map(|line| line.strip_prefix("data: "))
+        .expect("Event has no data");
+    let received: PaperEvent = serde_json::from_str(data).unwrap();
+    assert_eq!(received.id, event.id);
+    assert_eq!(received.categories, vec!["cs.LG"]);
+}
+
+#[tokio::test]
+async fn has_code_follows_implementations() {
+    let mut db = TestDb::connect().await;
+
+    let title = format!("Has code test {}", uuid::Uuid::new_v4().simple());
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar("INSERT INTO papers (title) VALUES ($1) RETURNING id").bind(&title),
         )
+        .await;
+
+    let has_code = || async {
+        let (has_code,): (bool,) = sqlx::query_as("SELECT has_code FROM papers WHERE id = $1")
+            .bind(paper_id)
+            .fetch_one(&db.pool)
+            .await
+            .expect("Failed to fetch paper");
+        has_code
+    };
+    assert!(!has_code().await);
+
+    let (implementation_id,): (uuid::Uuid,) = sqlx::query_as(
+        "INSERT INTO implementations (paper_id, github_url) VALUES ($1, $2) RETURNING id",
+    )
+    .bind(paper_id)
+    .bind("https://github.com/cwp-test/has-code")
+    .fetch_one(&db.pool)
+    .await
+    .expect("Failed to insert implementation");
+    assert!(has_code().await);
+
+    // The filter only matches while the paper has code
+    let search_count = |filter: bool| {
+        let app = db.app();
+        let uri = format!("/api/papers?q={}&has_code={}", title.replace(' ', "+"), filter);
+        async move {
+            let (status, json) = get_json(app, &uri).await;
+            assert_eq!(status, StatusCode::OK);
+            json["papers"].as_array().unwrap().len()
+        }
+    };
+    assert_eq!(search_count(true).await, 1);
+    assert_eq!(search_count(false).await, 0);
+
+    // Soft-deleting the only implementation clears the flag
+    sqlx::query("UPDATE implementations SET deleted_at = NOW() WHERE id = $1")
+        .bind(implementation_id)
+        .execute(&db.pool)
         .await
+        .expect("Failed to soft-delete implementation");
+    assert!(!has_code().await);
+    assert_eq!(search_count(true).await, 0);
+    assert_eq!(search_count(false).await, 1);
+}
+
+#[tokio::test]
+async fn stats_report_coverage_and_freshness() {
+    let mut db = TestDb::connect().await;
+
+    let source = format!("test-{}", uuid::Uuid::new_v4().simple());
+    let started_at = chrono::Utc::now() - chrono::Duration::minutes(5);
+    record_ingestion_run(&db.pool, &source, started_at, 3)
+        .await
+        .expect("Failed to record ingestion run");
+
+    let run_id: uuid::Uuid = sqlx::query_scalar("SELECT id FROM ingestion_runs WHERE source = $1")
+        .bind(&source)
+        .fetch_one(&db.pool)
+        .await
+        .expect("Ingestion run was not recorded");
+    db.track("ingestion_runs", run_id);
+
+    let (status, json) = get_json(db.app(), "/api/stats").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(json["results_count"].is_i64());
+    let percent = json["papers_with_code_percent"].as_f64().unwrap();
+    assert!((0.0..=100.0).contains(&percent));
+
+    let finished_at: chrono::DateTime<chrono::Utc> =
+        serde_json::from_value(json["last_ingested_at"][&source].clone())
+            .expect("Source missing from last_ingested_at");
+    assert!(finished_at > started_at);
+}
+
+#[tokio::test]
+async fn search_index_can_be_inspected_and_optimized() {
+    use backend::search::SearchIndex;
+    use backend::Paper;
+    use std::sync::Arc;
+
+    let mut db = TestDb::connect().await;
+
+    let path = db.temp_dir("cwp-index");
+    let search_index = SearchIndex::create(&path).expect("Failed to create index");
+
+    // Two commits leave two segments
+    for title in ["First indexed paper", "Second indexed paper"] {
+        let paper: Paper = serde_json::from_value(serde_json::json!({
+            "id": uuid::Uuid::new_v4(),
+            "title": title,
+            "has_code": false,
+        }))
         .unwrap();
+        let mut writer = search_index.writer().unwrap();
+        writer.add_document(search_index.paper_to_document(&paper)).unwrap();
+        writer.commit().unwrap();
+        writer.wait_merging_threads().unwrap();
+    }
+
+    let config = AppConfig {
+        admin_token: Some("test-admin-token".to_string()),
+        ..Default::default()
+    };
+    let app = create_app(db.pool.clone(), Some(Arc::new(search_index)), None, config);
+
+    let (status, json) = get_json(app.clone(), "/api/search/status").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["available"], true);
+    assert_eq!(json["num_docs"], 2);
+    assert_eq!(json["num_segments"], 2);
 
+    let optimize = |token: Option<&str>| {
+        let mut request = Request::builder()
+            .method("POST")
+            .uri("/api/admin/search/optimize");
+        if let Some(token) = token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+        send(app.clone(), request.body(Body::empty()).unwrap())
+    };
+
+    let response = optimize(None).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let (status, json) = into_json(optimize(Some("test-admin-token")).await).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["num_docs"], 2);
+    assert_eq!(json["num_segments"], 1);
+}
+
+#[tokio::test]
+async fn reads_fall_back_to_primary_while_replica_is_down() {
+    use backend::postgres::PgRepository;
+    use backend::{create_app_with_repository, ReadReplica};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let db = TestDb::connect().await;
+
+    let down = ReadReplica::new(
+        PgPoolOptions::new()
+            .connect_lazy("postgres://postgres@127.0.0.1:1/cwp")
+            .unwrap(),
+        Duration::from_millis(50),
+    );
+    let up = ReadReplica::new(
+        PgPoolOptions::new().connect_lazy(&db.database_url).unwrap(),
+        Duration::from_millis(50),
+    );
+
+    for _ in 0..40 {
+        if up.is_healthy() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    assert!(up.is_healthy());
+    assert!(!down.is_healthy());
+
+    for replica in [down, up] {
+        let repository = PgRepository::new(db.pool.clone(), Some(replica));
+        let app = create_app_with_repository(Arc::new(repository), None, None, Default::default());
+        let response = get(app, "/api/papers").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
+
+#[tokio::test]
+async fn dataset_files_are_listed_with_checksums() {
+    let mut db = TestDb::connect().await;
+
+    let name = format!("Dataset files test {}", uuid::Uuid::new_v4().simple());
+    let dataset_id = db
+        .insert(
+            "datasets",
+            sqlx::query_scalar("INSERT INTO datasets (name) VALUES ($1) RETURNING id").bind(&name),
+        )
+        .await;
+
+    // Two mirrors of train.parquet and a file without a known checksum
+    let checksum = "a".repeat(64);
+    for (url, sha256) in [
+        ("https://example.org/a/train.parquet", Some(checksum.as_str())),
+        ("https://mirror.example.org/train.parquet?download=1", Some(checksum.as_str())),
+        ("https://example.org/a/README.txt", None),
+    ] {
+        sqlx::query(
+            r#"
+            INSERT INTO dataset_files (dataset_id, url, format, size_bytes, sha256, source)
+            VALUES ($1, $2, 'parquet', 1024, $3, 'submission')
+            "#,
+        )
+        .bind(dataset_id)
+        .bind(url)
+        .bind(sha256)
+        .execute(&db.pool)
+        .await
+        .expect("Failed to insert dataset file");
+    }
+
+    // Checksums must be lowercase hex
+    let invalid = sqlx::query(
+        "INSERT INTO dataset_files (dataset_id, url, sha256, source) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(dataset_id)
+    .bind("https://example.org/a/bad.csv")
+    .bind("not-a-checksum")
+    .bind("submission")
+    .execute(&db.pool)
+    .await;
+    assert!(invalid.is_err());
+
+    let (status, files) = get_json(db.app(), &format!("/api/datasets/{}/files", dataset_id)).await;
+    assert_eq!(status, StatusCode::OK);
+    let files = files.as_array().unwrap();
+    assert_eq!(files.len(), 3);
+    assert_eq!(files[0]["url"], "https://example.org/a/README.txt");
+    assert_eq!(files[1]["size_bytes"], 1024);
+    assert_eq!(files[1]["sha256"], checksum.as_str());
+
+    let uri = format!("/api/datasets/{}/files/sha256sums", dataset_id);
+    let response = get(db.app(), &uri).await;
     assert_eq!(response.status(), StatusCode::OK);
-    
-    // We could parse the body here if we wanted to be sure it returns JSON
+    assert_eq!(
+        String::from_utf8(body_bytes(response).await.to_vec()).unwrap(),
+        format!("{}  train.parquet\n", checksum)
+    );
+
+    let uri = format!("/api/datasets/{}/files", uuid::Uuid::new_v4());
+    let response = get(db.app(), &uri).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn task_taxonomy_rolls_up_counts() {
+    let mut db = TestDb::connect().await;
+
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let area = format!("Taxonomy area {}", suffix);
+    let task = format!("Taxonomy task {}", suffix);
+    let subtask = format!("Taxonomy subtask {}", suffix);
+
+    let paper_id = db
+        .insert(
+            "papers",
+            sqlx::query_scalar("INSERT INTO papers (title) VALUES ($1) RETURNING id")
+                .bind(format!("Taxonomy paper {}", suffix)),
+        )
+        .await;
+
+    // Benchmarks create their task nodes; the paper has results on both
+    for task_name in [&task, &subtask] {
+        let benchmark_id = db
+            .insert(
+                "benchmarks",
+                sqlx::query_scalar(
+                    "INSERT INTO benchmarks (name, task) VALUES ($1, $2) RETURNING id",
+                )
+                .bind(format!("{} benchmark", task_name))
+                .bind(task_name),
+            )
+            .await;
+        sqlx::query(
+            r#"
+            INSERT INTO benchmark_results (paper_id, benchmark_id, metric_name, metric_value)
+            VALUES ($1, $2, 'accuracy', 90.0)
+            "#,
+        )
+        .bind(paper_id)
+        .bind(benchmark_id)
+        .execute(&db.pool)
+        .await
+        .expect("Failed to insert benchmark result");
+    }
+
+    let pool = db.pool.clone();
+    let task_id = |name: String| {
+        let pool = pool.clone();
+        async move {
+            let id: uuid::Uuid = sqlx::query_scalar("SELECT id FROM tasks WHERE name = $1")
+                .bind(name)
+                .fetch_one(&pool)
+                .await
+                .expect("Benchmark task was not added to the taxonomy");
+            id
+        }
+    };
+    let task_node = task_id(task.clone()).await;
+    let subtask_node = task_id(subtask.clone()).await;
+    db.track("tasks", task_node);
+    db.track("tasks", subtask_node);
+
+    let config = AppConfig {
+        admin_token: Some("test-admin-token".to_string()),
+        ..Default::default()
+    };
+    let app = db.app_with(config);
+
+    let set_parent = |id: uuid::Uuid, parent: Option<&str>, token: Option<&str>| {
+        let mut request = Request::builder()
+            .method("PUT")
+            .uri(format!("/api/admin/tasks/{}/parent", id))
+            .header("Content-Type", "application/json");
+        if let Some(token) = token {
+            request = request.header("Authorization", format!("Bearer {}", token));
+        }
+        let body = serde_json::json!({ "parent": parent }).to_string();
+        let request = request.body(Body::from(body)).unwrap();
+        let app = app.clone();
+        async move { into_json(send(app, request).await).await }
+    };
+
+    let (status, _) = set_parent(subtask_node, Some(&task), None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let token = Some("test-admin-token");
+    let (status, json) = set_parent(subtask_node, Some(&task), token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["parent_id"], task_node.to_string());
+
+    // The area doesn't exist yet and is created as a top-level task
+    let (status, json) = set_parent(task_node, Some(&area), token).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["ancestors"][0]["name"], area.as_str());
+    assert_eq!(json["children"][0]["name"], subtask.as_str());
+
+    // A task can't be moved under its own subtask
+    let area_node = task_id(area.clone()).await;
+    db.track("tasks", area_node);
+    let (status, _) = set_parent(area_node, Some(&subtask), token).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, json) = get_json(app.clone(), &format!("/api/tasks/{}", area_node)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["benchmark_count"], 2);
+    assert_eq!(json["paper_count"], 1);
+    assert_eq!(json["children"][0]["benchmark_count"], 2);
+    assert_eq!(json["children"][0]["children"][0]["benchmark_count"], 1);
+
+    let (status, json) = get_json(app.clone(), &format!("/api/tasks/{}", subtask_node)).await;
+    assert_eq!(status, StatusCode::OK);
+    let ancestors: Vec<&str> = json["ancestors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|ancestor| ancestor["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(ancestors, [area.as_str(), task.as_str()]);
+
+    let (status, json) = get_json(app, "/api/tasks").await;
+    assert_eq!(status, StatusCode::OK);
+    let roots = json.as_array().unwrap();
+    assert!(roots.iter().any(|root| root["name"] == area.as_str()));
+    assert!(!roots.iter().any(|root| root["name"] == task.as_str()));
 }
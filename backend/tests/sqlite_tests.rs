@@ -0,0 +1,257 @@
+use backend::{create_app_with_repository, sqlite, AppConfig};
+use sqlx::SqlitePool;
+use std::env;
+use std::path::PathBuf;
+use std::sync::Arc;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use tower::ServiceExt; // for `oneshot`
+
+/// A seeded SQLite database in a scratch directory, removed on drop.
+struct TestDb {
+    pool: SqlitePool,
+    dir: PathBuf,
+}
+
+impl TestDb {
+    async fn seeded() -> Self {
+        let dir = env::temp_dir().join(format!("cwp-sqlite-{}", uuid::Uuid::new_v4().simple()));
+        // The directory doesn't exist yet; connect creates it
+        let url = format!("sqlite://{}", dir.join("cwp.db").display());
+
+        let pool = sqlite::connect(&url).await.expect("Failed to open SQLite database");
+        sqlite::seed(&pool).await.expect("Failed to seed");
+
+        Self { pool, dir }
+    }
+
+    fn app(&self) -> Router {
+        let repository = sqlite::SqliteRepository::new(self.pool.clone());
+        let config = AppConfig {
+            admin_token: Some("test-admin-token".to_string()),
+            ..Default::default()
+        };
+        create_app_with_repository(Arc::new(repository), None, None, config)
+    }
+}
+
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+async fn get_json(app: Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+async fn set_parent(app: Router, task_id: &str, parent: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method("PUT")
+        .uri(format!("/api/admin/tasks/{}/parent", task_id))
+        .header("Authorization", "Bearer test-admin-token")
+        .header("Content-Type", "application/json")
+        .body(Body::from(serde_json::json!({ "parent": parent }).to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+#[tokio::test]
+async fn seed_is_idempotent() {
+    let db = TestDb::seeded().await;
+
+    let stats = sqlite::seed(&db.pool).await.expect("Failed to re-seed");
+    let (status, json) = get_json(db.app(), "/api/stats").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["papers_count"], stats.papers);
+    assert_eq!(json["results_count"], stats.results);
+    // Every sample paper but Adam has code; ViT v1 through its v2
+    assert_eq!(json["papers_with_code_count"], stats.papers - 1);
+}
+
+#[tokio::test]
+async fn serves_papers_from_sqlite() {
+    let db = TestDb::seeded().await;
+
+    let (status, json) = get_json(db.app(), "/api/papers?limit=100").await;
+    assert_eq!(status, StatusCode::OK);
+    let titles: Vec<&str> = json["papers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|paper| paper["title"].as_str().unwrap())
+        .collect();
+    // Newest first, with only the latest ViT version (from 2021) listed
+    assert_eq!(titles.len(), 10);
+    assert!(titles[0].starts_with("An Image is Worth"));
+    assert!(!titles[1..].iter().any(|title| title.starts_with("An Image is Worth")));
+
+    let (status, json) = get_json(db.app(), "/api/papers?q=residual&has_code=true").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["papers"].as_array().unwrap().len(), 1);
+    assert_eq!(json["papers"][0]["arxiv_id"], "1512.03385v1");
+
+    let (_, json) = get_json(db.app(), "/api/papers?has_code=false").await;
+    assert_eq!(json["papers"].as_array().unwrap().len(), 1);
+    assert_eq!(json["papers"][0]["arxiv_id"], "1412.6980v9");
+}
+
+#[tokio::test]
+async fn paper_details_span_arxiv_versions() {
+    let db = TestDb::seeded().await;
+
+    let (status, paper) = get_json(db.app(), "/api/papers/by-arxiv/2010.11929v1").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(paper["arxiv_id"], "2010.11929v2");
+    assert_eq!(paper["has_code"], true);
+    assert_eq!(paper["versions"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        paper["implementations"][0]["github_url"],
+        "https://github.com/google-research/vision_transformer"
+    );
+    assert_eq!(paper["languages"][0]["language"], "Jupyter Notebook");
+
+    // v1 has no code of its own but shares v2's
+    let v1 = paper["versions"][1]["id"].as_str().unwrap();
+    let (status, json) = get_json(db.app(), &format!("/api/papers/{}/implementations", v1)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json[0]["rank"], 1);
+    assert_eq!(json[0]["is_official"], true);
+}
+
+#[tokio::test]
+async fn serves_datasets_benchmarks_and_results() {
+    let db = TestDb::seeded().await;
+
+    let (status, datasets) = get_json(db.app(), "/api/datasets?search=imagenet").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(datasets[0]["modalities"], serde_json::json!(["Images"]));
+
+    let (_, benchmarks) = get_json(db.app(), "/api/benchmarks?search=detection").await;
+    let benchmark_id = benchmarks[0]["id"].as_str().unwrap();
+    let uri = format!("/api/benchmarks/{}", benchmark_id);
+    let (status, benchmark) = get_json(db.app(), &uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(benchmark["dataset"]["name"], "COCO");
+
+    let (status, results) = get_json(db.app(), "/api/benchmark-results").await;
+    assert_eq!(status, StatusCode::OK);
+    let values: Vec<&str> = results
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| result["metric_value"].as_str().unwrap())
+        .collect();
+    assert_eq!(values, ["88.55", "78.57", "44.9", "34.9", "28.4"]);
+
+    let (_, implementations) = get_json(db.app(), "/api/implementations?archived=false").await;
+    assert!(implementations
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|implementation| implementation["archived"] != true));
+}
+
+#[tokio::test]
+async fn task_taxonomy_works_on_sqlite() {
+    let db = TestDb::seeded().await;
+
+    let (status, tasks) = get_json(db.app(), "/api/tasks").await;
+    assert_eq!(status, StatusCode::OK);
+    let detection = tasks
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|task| task["name"] == "Object Detection")
+        .unwrap();
+    assert_eq!(detection["benchmark_count"], 1);
+    assert_eq!(detection["paper_count"], 2);
+    let detection_id = detection["id"].as_str().unwrap();
+
+    let (status, task) = set_parent(db.app(), detection_id, "Computer Vision").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(task["ancestors"][0]["name"], "Computer Vision");
+
+    let (_, tasks) = get_json(db.app(), "/api/tasks").await;
+    let vision = tasks
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|task| task["name"] == "Computer Vision")
+        .unwrap();
+    assert_eq!(vision["paper_count"], 2);
+    assert_eq!(vision["children"][0]["name"], "Object Detection");
+
+    // Computer Vision can't go under its own subtask
+    let vision_id = vision["id"].as_str().unwrap();
+    let (status, json) = set_parent(db.app(), vision_id, "Object Detection").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(json["error"].as_str().unwrap().contains("its own subtask"));
+}
+
+#[tokio::test]
+async fn badges_authors_and_dois_work_on_sqlite() {
+    let db = TestDb::seeded().await;
+
+    let (status, badge) = get_json(db.app(), "/api/badge/paper/2010.11929").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(badge["message"], "88.55 Top 1 Accuracy on ImageNet");
+
+    let (_, badge) = get_json(db.app(), "/api/badge/paper/1512.03385").await;
+    assert_eq!(badge["message"], "2 implementations");
+
+    // The ORCID is on v1 only, the DOI on v2
+    sqlx::query(
+        r#"
+        INSERT INTO paper_authors (paper_id, position, name, orcid, source)
+        SELECT id, 0, 'Alexey Dosovitskiy', '0000-0002-1825-0097', 'orcid'
+        FROM papers WHERE arxiv_id = '2010.11929v1'
+        "#,
+    )
+    .execute(&db.pool)
+    .await
+    .unwrap();
+    sqlx::query("UPDATE papers SET doi = '10.48550/arxiv.2010.11929' WHERE arxiv_id = $1")
+        .bind("2010.11929v2")
+        .execute(&db.pool)
+        .await
+        .unwrap();
+
+    let (status, author) = get_json(db.app(), "/api/authors/orcid/0000-0002-1825-0097").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(author["name"], "Alexey Dosovitskiy");
+    assert_eq!(author["papers"].as_array().unwrap().len(), 1);
+    assert_eq!(author["papers"][0]["arxiv_id"], "2010.11929v2");
+
+    let (status, paper) = get_json(db.app(), "/api/papers/by-doi/10.48550/ARXIV.2010.11929").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(paper["author_orcids"][0]["orcid"], "0000-0002-1825-0097");
+}
+
+#[tokio::test]
+async fn paper_stream_needs_postgres() {
+    let db = TestDb::seeded().await;
+
+    let (status, json) = get_json(db.app(), "/api/papers/stream").await;
+    assert_eq!(status, StatusCode::NOT_IMPLEMENTED);
+    assert!(json["error"].as_str().unwrap().contains("Postgres"));
+
+    let (status, _) = get_json(db.app(), "/missing").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}